@@ -19,6 +19,13 @@ interface NgaEntry {
   artist: string;
   date: string;
   medium: string;
+  objectid?: string;
+  accessionnum?: string;
+  creditline?: string;
+  dimensions?: string;
+  department?: string;
+  width?: number;
+  height?: number;
 }
 
 function parseCSV(text: string): Record<string, string>[] {
@@ -141,6 +148,13 @@ async function main() {
       artist: obj.attribution || obj.attributioninverted || "Unknown Artist",
       date: obj.displaydate || "",
       medium: obj.medium || "",
+      objectid: objectId,
+      accessionnum: obj.accessionnum || undefined,
+      creditline: obj.creditline || undefined,
+      dimensions: obj.dimensions || undefined,
+      department: obj.departmentabbr || undefined,
+      width: Number(img.width) || undefined,
+      height: Number(img.height) || undefined,
     });
   }
 
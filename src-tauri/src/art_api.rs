@@ -1,7 +1,15 @@
+use crate::error::ArtError;
 use crate::Artwork;
+use async_trait::async_trait;
+use rand::seq::IndexedRandom;
 use rand::Rng;
-use reqwest::Client;
-use serde::Deserialize;
+use reqwest::{Client, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 /// Strip HTML tags from a string
 fn strip_html(s: &str) -> String {
@@ -18,18 +26,58 @@ fn strip_html(s: &str) -> String {
     result
 }
 
+/// `None` for missing or blank strings, so optional metadata stays absent rather than empty
+fn non_empty(s: Option<String>) -> Option<String> {
+    s.map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+/// Send an API request and decode the JSON body, mapping failures to `ArtError`.
+///
+/// Some sources take the user's API key in the query string, so errors never
+/// carry it: the URL is dropped from reqwest errors and its query from `HttpStatus`.
+async fn get_json<T: DeserializeOwned>(source_name: &str, req: RequestBuilder) -> Result<T, ArtError> {
+    let resp = req
+        .send()
+        .await
+        .map_err(|e| ArtError::network(source_name, e.without_url()))?;
+    if !resp.status().is_success() {
+        let mut url = resp.url().clone();
+        url.set_query(None);
+        return Err(ArtError::HttpStatus {
+            source_name: source_name.into(),
+            status: resp.status().as_u16(),
+            url: url.to_string(),
+        });
+    }
+    resp.json().await.map_err(|e| {
+        let e = e.without_url();
+        // A connection dropped mid-body is a network failure, not a bad response
+        if e.is_body() || e.is_timeout() {
+            ArtError::network(source_name, e)
+        } else {
+            ArtError::parse(source_name, e)
+        }
+    })
+}
+
 /// Download image bytes with validation: checks HTTP status, content-type, and minimum size.
-/// Returns (bytes, mime_type) or None on any failure.
-async fn download_image(client: &Client, url: &str) -> Option<(Vec<u8>, String)> {
+async fn download_image(client: &Client, url: &str) -> Result<ImageData, ArtError> {
+    let rejected = |reason: String| {
+        log::warn!("Image rejected ({reason}): {url}");
+        ArtError::ImageRejected {
+            url: url.into(),
+            reason,
+        }
+    };
+
     let mut req = client.get(url);
     // AIC's IIIF server requires a Referer header
     if url.contains("artic.edu") {
         req = req.header("Referer", "https://www.artic.edu/");
     }
-    let resp = req.send().await.ok()?;
+    let resp = req.send().await.map_err(|e| ArtError::network("image", e))?;
     if !resp.status().is_success() {
-        log::warn!("Image HTTP {}: {}", resp.status(), url);
-        return None;
+        return Err(rejected(format!("HTTP {}", resp.status())));
     }
     let content_type = resp
         .headers()
@@ -38,13 +86,14 @@ async fn download_image(client: &Client, url: &str) -> Option<(Vec<u8>, String)>
         .unwrap_or("")
         .to_string();
     if !content_type.starts_with("image/") {
-        log::warn!("Not an image ({}): {}", content_type, url);
-        return None;
+        return Err(rejected(format!("not an image: {content_type}")));
     }
-    let bytes = resp.bytes().await.ok()?;
+    let bytes = resp
+        .bytes()
+        .await
+        .map_err(|e| ArtError::network("image", e))?;
     if bytes.len() < 1000 {
-        log::warn!("Image too small ({} bytes): {}", bytes.len(), url);
-        return None;
+        return Err(rejected(format!("too small: {} bytes", bytes.len())));
     }
     // Extract just the mime type (strip charset etc.)
     let mime = content_type
@@ -53,7 +102,487 @@ async fn download_image(client: &Client, url: &str) -> Option<(Vec<u8>, String)>
         .unwrap_or("image/jpeg")
         .trim()
         .to_string();
-    Some((bytes.to_vec(), mime))
+    Ok(ImageData {
+        bytes: bytes.to_vec(),
+        mime,
+    })
+}
+
+/// Pixel size of a downloaded image, read from its header. Scaled downloads keep
+/// the original's proportions, which is all the aspect-ratio matching needs.
+fn downloaded_size(image: &ImageData) -> Option<ImageSize> {
+    let size = imagesize::blob_size(&image.bytes).ok()?;
+    Some(ImageSize {
+        width: u32::try_from(size.width).ok()?,
+        height: u32::try_from(size.height).ok()?,
+    })
+}
+
+// ── Source abstraction ──
+
+/// Raw image bytes as downloaded
+pub struct ImageData {
+    pub bytes: Vec<u8>,
+    pub mime: String,
+}
+
+/// Bounding box, in physical pixels, that downloaded images should fill
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImageSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ImageSize {
+    /// Largest box we ever ask for; beyond this downloads get slow for little visible gain
+    pub const MAX: ImageSize = ImageSize {
+        width: 5120,
+        height: 5120,
+    };
+
+    /// Used until the monitors have been measured
+    pub const DEFAULT: ImageSize = ImageSize {
+        width: 1920,
+        height: 1080,
+    };
+
+    /// Scale down to fit `ImageSize::MAX`, keeping the aspect ratio
+    pub fn capped(self) -> Self {
+        let (width, height) = (self.width.max(1), self.height.max(1));
+        let scale = (Self::MAX.width as f64 / width as f64)
+            .min(Self::MAX.height as f64 / height as f64)
+            .min(1.0);
+        Self {
+            width: ((width as f64 * scale).round() as u32).max(1),
+            height: ((height as f64 * scale).round() as u32).max(1),
+        }
+    }
+
+    pub fn longest_side(self) -> u32 {
+        self.width.max(self.height)
+    }
+
+    /// Width divided by height
+    pub fn aspect_ratio(self) -> f64 {
+        self.width.max(1) as f64 / self.height.max(1) as f64
+    }
+
+    /// IIIF size parameter that fits the image inside this box, keeping its aspect ratio
+    pub fn iiif_fit(self) -> String {
+        format!("!{},{}", self.width, self.height)
+    }
+}
+
+/// Highest weight a source can be given
+pub const MAX_SOURCE_WEIGHT: u32 = 10;
+
+/// Most search terms a source can be given
+pub const MAX_SEARCH_TERMS: usize = 20;
+
+const MAX_SEARCH_TERM_LEN: usize = 100;
+
+/// A source-specific filter users can set alongside search terms
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct SourceFilter {
+    /// Query parameter it maps to, e.g. "departmentId"
+    pub key: &'static str,
+    pub label: &'static str,
+    /// Whether the value must be a whole number
+    pub numeric: bool,
+    /// Value used while the filter isn't set, if any
+    pub default: Option<&'static str>,
+}
+
+/// Whether a source is used, and how often relative to the others
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SourcePrefs {
+    pub enabled: bool,
+    /// 1 to `MAX_SOURCE_WEIGHT`; a source with weight 2 is picked twice as often as one with 1
+    pub weight: u32,
+    /// Search terms to pick from instead of the source's defaults
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub terms: Vec<String>,
+    /// Values for the source's `filters`, by key
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub filters: BTreeMap<String, String>,
+    /// Key for sources whose API requires one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    /// Directory for sources that read local files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+}
+
+impl Default for SourcePrefs {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            weight: 1,
+            terms: Vec::new(),
+            filters: BTreeMap::new(),
+            api_key: None,
+            folder: None,
+        }
+    }
+}
+
+impl SourcePrefs {
+    /// A random search term: one of the user's, or else one of `defaults`
+    pub fn pick_term<'a>(&'a self, defaults: &[&'static str]) -> &'a str {
+        let mut rng = rand::rng();
+        self.terms
+            .choose(&mut rng)
+            .map(String::as_str)
+            .or_else(|| defaults.choose(&mut rng).copied())
+            .unwrap_or_default()
+    }
+
+    pub fn filter(&self, key: &str) -> Option<&str> {
+        self.filters.get(key).map(String::as_str)
+    }
+
+    /// The API key, unless it's blank
+    pub fn api_key(&self) -> Option<&str> {
+        self.api_key.as_deref().map(str::trim).filter(|k| !k.is_empty())
+    }
+
+    pub fn folder(&self) -> Option<PathBuf> {
+        self.folder
+            .as_deref()
+            .map(str::trim)
+            .filter(|f| !f.is_empty())
+            .map(PathBuf::from)
+    }
+
+    /// Whether `source` can be drawn from: enabled, with the API key or folder it needs
+    fn is_usable(&self, source: &dyn ArtSource) -> bool {
+        let caps = source.capabilities();
+        self.enabled
+            && (!caps.api_key || self.api_key().is_some())
+            && (!caps.folder || self.folder().is_some())
+    }
+
+    /// Check the terms and filters against what `source` supports
+    fn validate_query(&self, source: &dyn ArtSource) -> Result<(), ArtError> {
+        let name = source.name();
+        if !self.terms.is_empty() && !source.capabilities().search {
+            return Err(ArtError::invalid(format!("{name} doesn't support search terms")));
+        }
+        if self.terms.len() > MAX_SEARCH_TERMS {
+            return Err(ArtError::invalid(format!(
+                "{name} can have at most {MAX_SEARCH_TERMS} search terms"
+            )));
+        }
+        for term in &self.terms {
+            if term.trim().is_empty() {
+                return Err(ArtError::invalid(format!("{name} search terms can't be blank")));
+            }
+            if term.len() > MAX_SEARCH_TERM_LEN {
+                return Err(ArtError::invalid(format!(
+                    "{name} search term '{term}' is too long"
+                )));
+            }
+        }
+
+        for (key, value) in &self.filters {
+            let filter = source
+                .filters()
+                .iter()
+                .find(|f| f.key == key)
+                .ok_or_else(|| ArtError::invalid(format!("{name} has no '{key}' filter")))?;
+            if value.trim().is_empty() {
+                return Err(ArtError::invalid(format!("{name} {} can't be blank", filter.label)));
+            }
+            if filter.numeric && value.trim().parse::<u32>().is_err() {
+                return Err(ArtError::invalid(format!(
+                    "{name} {} must be a number",
+                    filter.label
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Preferences for each source by id prefix, saved under `sources` in `settings.json`.
+/// Sources without an entry use the defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SourceSettings(pub BTreeMap<String, SourcePrefs>);
+
+impl SourceSettings {
+    pub fn get(&self, id_prefix: &str) -> SourcePrefs {
+        self.0.get(id_prefix).cloned().unwrap_or_default()
+    }
+
+    /// Whether the source an artwork id belongs to is enabled
+    pub fn allows(&self, artwork_id: &str) -> bool {
+        artwork_id
+            .split_once('-')
+            .map_or(true, |(prefix, _)| self.get(prefix).enabled)
+    }
+}
+
+/// Everything a source needs for one fetch
+#[derive(Clone)]
+pub struct FetchContext {
+    pub client: Client,
+    /// How large the image will be displayed; sources with a choice of sizes should match it
+    pub target: ImageSize,
+    pub sources: Arc<SourceSettings>,
+}
+
+impl FetchContext {
+    /// The user's preferences for the source with this id prefix
+    pub fn prefs(&self, id_prefix: &str) -> SourcePrefs {
+        self.sources.get(id_prefix)
+    }
+}
+
+/// What a source hands back: the artwork's metadata plus its image.
+///
+/// Sources can leave `artwork.image_url` empty; the registry points it at the
+/// `art://` protocol once the image has somewhere to live.
+pub struct FetchedArtwork {
+    pub artwork: Artwork,
+    pub image: ImageData,
+}
+
+/// Optional features a source supports beyond `fetch_random`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SourceCapabilities {
+    /// `fetch_by_id` can look up a specific object
+    pub fetch_by_id: bool,
+    /// Images come from a IIIF image server, so the download size can be chosen
+    pub iiif: bool,
+    /// `fetch_random` honours the user's search terms
+    pub search: bool,
+    /// The API needs a key from the user; the source is skipped until one is set
+    pub api_key: bool,
+    /// Reads from a folder the user picks; the source is skipped until one is set
+    pub folder: bool,
+}
+
+/// A collection that artwork can be pulled from.
+///
+/// Every `Artwork` a source produces must have an id of the form
+/// `{id_prefix}-{native_id}` so it can be routed back to the source later.
+#[async_trait]
+pub trait ArtSource: Send + Sync {
+    /// Short name used in logs, e.g. "Met"
+    fn name(&self) -> &str;
+
+    /// Prefix of the artwork ids this source produces, without the dash, e.g. "met"
+    fn id_prefix(&self) -> &str;
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities::default()
+    }
+
+    /// Search terms `fetch_random` picks from when the user hasn't set any
+    fn default_terms(&self) -> &[&'static str] {
+        &[]
+    }
+
+    /// Filters users can set for this source
+    fn filters(&self) -> &[SourceFilter] {
+        &[]
+    }
+
+    /// Fetch a random artwork, image included
+    async fn fetch_random(&self, ctx: &FetchContext) -> Result<FetchedArtwork, ArtError>;
+
+    /// Fetch a specific artwork by its native id (the part after the prefix)
+    async fn fetch_by_id(&self, _ctx: &FetchContext, _id: &str) -> Result<FetchedArtwork, ArtError> {
+        Err(ArtError::unsupported(format!(
+            "{} does not support lookup by id",
+            self.name()
+        )))
+    }
+
+    /// Page for an artwork on the museum's website, given its native id
+    fn object_url(&self, _id: &str) -> Option<String> {
+        None
+    }
+}
+
+/// The set of sources `ArtCache` draws from.
+#[derive(Default)]
+pub struct SourceRegistry {
+    sources: Vec<Box<dyn ArtSource>>,
+}
+
+impl SourceRegistry {
+    /// An empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A registry with all built-in museum sources
+    pub fn with_builtin_sources() -> Self {
+        let mut registry = Self::new();
+        registry.register(MetSource);
+        registry.register(AicSource);
+        registry.register(CmaSource);
+        registry.register(NgaSource);
+        registry.register(RijksSource);
+        registry.register(SmithsonianSource);
+        registry.register(HarvardSource);
+        registry.register(CommonsSource);
+        registry.register(LocalFolderSource::default());
+        registry
+    }
+
+    /// Add a source, replacing any existing source with the same id prefix
+    pub fn register(&mut self, source: impl ArtSource + 'static) {
+        self.sources.retain(|s| s.id_prefix() != source.id_prefix());
+        self.sources.push(Box::new(source));
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn ArtSource> {
+        self.sources.iter().map(|s| s.as_ref())
+    }
+
+    pub fn get(&self, id_prefix: &str) -> Option<&dyn ArtSource> {
+        self.sources
+            .iter()
+            .find(|s| s.id_prefix() == id_prefix)
+            .map(|s| s.as_ref())
+    }
+
+    /// Check settings before saving them: every source is known, weights are in
+    /// range, and at least one source is enabled and has the API key or folder it needs
+    pub fn validate_settings(&self, settings: &SourceSettings) -> Result<(), ArtError> {
+        for (prefix, prefs) in &settings.0 {
+            let source = self
+                .get(prefix)
+                .ok_or_else(|| ArtError::invalid(format!("Unknown source '{prefix}'")))?;
+            if !(1..=MAX_SOURCE_WEIGHT).contains(&prefs.weight) {
+                return Err(ArtError::invalid(format!(
+                    "{} weight must be between 1 and {MAX_SOURCE_WEIGHT}",
+                    source.name()
+                )));
+            }
+            prefs.validate_query(source)?;
+            if prefs.api_key.is_some() && !source.capabilities().api_key {
+                return Err(ArtError::invalid(format!("{} doesn't use an API key", source.name())));
+            }
+            match prefs.folder() {
+                Some(_) if !source.capabilities().folder => {
+                    return Err(ArtError::invalid(format!("{} doesn't read a folder", source.name())));
+                }
+                Some(folder) if !folder.is_dir() => {
+                    return Err(ArtError::invalid(format!("{} is not a folder", folder.display())));
+                }
+                _ => {}
+            }
+        }
+        if !self.iter().any(|s| settings.get(s.id_prefix()).is_usable(s)) {
+            return Err(ArtError::invalid(
+                "Enable at least one source (sources that need an API key or folder count once it's set)",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Usable sources in the order to try them. Each position is a weighted
+    /// draw from the sources left, so heavier sources also come first as fallbacks.
+    fn pick_order(&self, settings: &SourceSettings) -> Vec<&dyn ArtSource> {
+        let mut left: Vec<(&dyn ArtSource, u32)> = self
+            .iter()
+            .filter_map(|s| {
+                let prefs = settings.get(s.id_prefix());
+                prefs.is_usable(s).then_some((s, prefs.weight.max(1)))
+            })
+            .collect();
+
+        let mut rng = rand::rng();
+        let mut order = Vec::with_capacity(left.len());
+        while !left.is_empty() {
+            let mut roll = rng.random_range(0..left.iter().map(|(_, w)| w).sum::<u32>());
+            let i = left
+                .iter()
+                .position(|&(_, w)| {
+                    if roll < w {
+                        return true;
+                    }
+                    roll -= w;
+                    false
+                })
+                .unwrap_or(0);
+            order.push(left.remove(i).0);
+        }
+        order
+    }
+
+    /// Fetch a random artwork from any enabled source
+    pub async fn fetch_random(&self, ctx: &FetchContext) -> Result<FetchedArtwork, ArtError> {
+        let order = self.pick_order(&ctx.sources);
+        if order.is_empty() {
+            return Err(ArtError::AllSourcesFailed {
+                errors: Vec::new(),
+                offline: false,
+            });
+        }
+
+        // Try a weighted random source first, then fall back to the others
+        let mut errors = Vec::new();
+        // Folder sources don't say anything about the network
+        let (mut remote, mut remote_unreachable) = (0, 0);
+        for source in order {
+            match source.fetch_random(ctx).await {
+                Ok(fetched) => return Ok(with_image_url(fetched)),
+                Err(e) => {
+                    log::warn!("{} failed: {e}", source.name());
+                    if !source.capabilities().folder {
+                        remote += 1;
+                        remote_unreachable += usize::from(e.is_connectivity());
+                    }
+                    errors.push(e);
+                }
+            }
+        }
+
+        Err(ArtError::AllSourcesFailed {
+            errors,
+            offline: remote > 0 && remote_unreachable == remote,
+        })
+    }
+
+    /// Fetch a specific artwork by its full id (e.g. "met-436535")
+    pub async fn fetch_by_id(
+        &self,
+        ctx: &FetchContext,
+        artwork_id: &str,
+    ) -> Result<FetchedArtwork, ArtError> {
+        let (source, id) = self.route(artwork_id)?;
+        source.fetch_by_id(ctx, id).await.map(with_image_url)
+    }
+
+    /// Museum web page for an artwork, by its full id
+    pub fn object_url(&self, artwork_id: &str) -> Result<String, ArtError> {
+        let (source, id) = self.route(artwork_id)?;
+        source.object_url(id).ok_or_else(|| {
+            ArtError::unsupported(format!("{} has no web page for {artwork_id}", source.name()))
+        })
+    }
+
+    /// The source an artwork id belongs to, and the native id within it
+    fn route<'a>(&self, artwork_id: &'a str) -> Result<(&dyn ArtSource, &'a str), ArtError> {
+        let (prefix, id) = artwork_id
+            .split_once('-')
+            .ok_or_else(|| ArtError::unsupported(format!("Malformed artwork id: {artwork_id}")))?;
+        let source = self
+            .get(prefix)
+            .ok_or_else(|| ArtError::unsupported(format!("No source registered for '{prefix}'")))?;
+        Ok((source, id))
+    }
+}
+
+fn with_image_url(mut fetched: FetchedArtwork) -> FetchedArtwork {
+    fetched.artwork.image_url = crate::protocol::image_url(&fetched.artwork.id, &fetched.image.bytes);
+    fetched
 }
 
 // ── Met Museum API ──
@@ -76,79 +605,192 @@ struct MetObject {
     medium: Option<String>,
     #[serde(rename = "primaryImage")]
     primary_image: Option<String>,
+    /// Web-sized rendition (roughly 800px on the long side)
+    #[serde(rename = "primaryImageSmall")]
+    primary_image_small: Option<String>,
+    #[serde(default)]
+    measurements: Option<Vec<MetMeasurement>>,
+    #[serde(rename = "creditLine")]
+    credit_line: Option<String>,
+    dimensions: Option<String>,
+    department: Option<String>,
+    culture: Option<String>,
+    #[serde(rename = "accessionNumber")]
+    accession_number: Option<String>,
+    #[serde(rename = "isPublicDomain")]
+    is_public_domain: Option<bool>,
+    #[serde(rename = "objectURL")]
+    object_url: Option<String>,
 }
 
-pub async fn fetch_met_artwork(client: &Client) -> Result<Artwork, String> {
-    let search_terms = [
-        "painting", "landscape", "portrait", "still life", "sculpture",
-        "impressionism", "renaissance", "abstract", "nature", "mythology",
-    ];
-    let term = search_terms[rand::rng().random_range(0..search_terms.len())];
-
-    let search: MetSearchResult = client
-        .get("https://collectionapi.metmuseum.org/public/collection/v1/search")
-        .query(&[("hasImages", "true"), ("q", term)])
-        .send()
-        .await
-        .map_err(|e| format!("Met search failed: {e}"))?
-        .json()
-        .await
-        .map_err(|e| format!("Met search parse failed: {e}"))?;
+#[derive(Deserialize)]
+struct MetMeasurement {
+    #[serde(rename = "elementMeasurements", default)]
+    element_measurements: std::collections::HashMap<String, f64>,
+}
 
-    let ids = search.object_ids.ok_or("No results from Met")?;
-    if ids.is_empty() {
-        return Err("Empty Met results".into());
+impl MetObject {
+    /// The web-sized image when it's enough for the target, otherwise the full original
+    fn image_url_for(&self, target: ImageSize) -> Option<&str> {
+        let full = self.primary_image.as_deref().filter(|u| !u.is_empty());
+        let small = self.primary_image_small.as_deref().filter(|u| !u.is_empty());
+        if target.longest_side() <= MET_SMALL_IMAGE_MAX {
+            small.or(full)
+        } else {
+            full.or(small)
+        }
     }
 
-    // Try up to 5 random objects to find one with an image
-    for _ in 0..5 {
-        let id = ids[rand::rng().random_range(0..ids.len())];
-        let url = format!(
-            "https://collectionapi.metmuseum.org/public/collection/v1/objects/{id}"
-        );
+    /// Proportions of the object (in millimetres), from its first height-and-width measurement
+    fn proportions(&self) -> Option<ImageSize> {
+        self.measurements.as_ref()?.iter().find_map(|m| {
+            let height = *m.element_measurements.get("Height")?;
+            let width = *m.element_measurements.get("Width")?;
+            (height > 0.0 && width > 0.0).then(|| ImageSize {
+                width: (width * 10.0).round().max(1.0) as u32,
+                height: (height * 10.0).round().max(1.0) as u32,
+            })
+        })
+    }
+}
 
-        let obj: MetObject = match client.get(&url).send().await {
-            Ok(resp) => match resp.json().await {
-                Ok(o) => o,
-                Err(_) => continue,
+/// Targets up to this size are served from `primaryImageSmall`
+const MET_SMALL_IMAGE_MAX: u32 = 1024;
+
+pub struct MetSource;
+
+impl MetSource {
+    async fn object_to_artwork(ctx: &FetchContext, obj: MetObject) -> Result<FetchedArtwork, ArtError> {
+        let image_url = obj
+            .image_url_for(ctx.target)
+            .ok_or_else(|| ArtError::no_results("Met", format!("object {} has no image", obj.object_id)))?;
+        let image = download_image(&ctx.client, image_url).await?;
+        let image_size = obj.proportions();
+
+        Ok(FetchedArtwork {
+            artwork: Artwork {
+                id: format!("met-{}", obj.object_id),
+                title: strip_html(&obj.title.unwrap_or_else(|| "Untitled".into())),
+                artist: obj
+                    .artist_display_name
+                    .filter(|s| !s.is_empty())
+                    .unwrap_or_else(|| "Unknown Artist".into()),
+                date: obj.object_date.unwrap_or_default(),
+                medium: obj.medium.unwrap_or_default(),
+                source: "The Metropolitan Museum of Art".into(),
+                image_url: String::new(),
+                image_size,
+                credit_line: non_empty(obj.credit_line),
+                dimensions: non_empty(obj.dimensions),
+                department: non_empty(obj.department),
+                culture: non_empty(obj.culture),
+                accession_number: non_empty(obj.accession_number),
+                public_domain: obj.is_public_domain,
+                license: None,
+                object_url: non_empty(obj.object_url),
             },
-            Err(_) => continue,
-        };
+            image,
+        })
+    }
+}
 
-        let image_url = match &obj.primary_image {
-            Some(url) if !url.is_empty() => url.clone(),
-            _ => continue,
-        };
+#[async_trait]
+impl ArtSource for MetSource {
+    fn name(&self) -> &str {
+        "Met"
+    }
 
-        let (image_bytes, mime) = match download_image(client, &image_url).await {
-            Some(result) => result,
-            None => continue, // try next random object
-        };
+    fn id_prefix(&self) -> &str {
+        "met"
+    }
 
-        let b64 = base64::Engine::encode(
-            &base64::engine::general_purpose::STANDARD,
-            &image_bytes,
-        );
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            fetch_by_id: true,
+            iiif: false,
+            search: true,
+            api_key: false,
+            folder: false,
+        }
+    }
 
-        return Ok(Artwork {
-            id: format!("met-{}", obj.object_id),
-            title: strip_html(&obj.title.unwrap_or_else(|| "Untitled".into())),
-            artist: obj
-                .artist_display_name
-                .filter(|s| !s.is_empty())
-                .unwrap_or_else(|| "Unknown Artist".into()),
-            date: obj.object_date.unwrap_or_default(),
-            medium: obj.medium.unwrap_or_default(),
-            source: "The Metropolitan Museum of Art".into(),
-            image_base64: format!("data:{mime};base64,{b64}"),
-        });
+    fn default_terms(&self) -> &[&'static str] {
+        &[
+            "painting", "landscape", "portrait", "still life", "sculpture",
+            "impressionism", "renaissance", "abstract", "nature", "mythology",
+        ]
+    }
+
+    fn filters(&self) -> &[SourceFilter] {
+        &[SourceFilter {
+            key: "departmentId",
+            label: "department id",
+            numeric: true,
+            default: None,
+        }]
+    }
+
+    async fn fetch_random(&self, ctx: &FetchContext) -> Result<FetchedArtwork, ArtError> {
+        let prefs = ctx.prefs(self.id_prefix());
+        let term = prefs.pick_term(self.default_terms());
+
+        let mut query = vec![("hasImages", "true"), ("q", term)];
+        if let Some(department) = prefs.filter("departmentId") {
+            query.push(("departmentId", department.trim()));
+        }
+        let search: MetSearchResult = get_json(
+            "Met",
+            ctx.client
+                .get("https://collectionapi.metmuseum.org/public/collection/v1/search")
+                .query(&query),
+        )
+        .await?;
+
+        let ids = search.object_ids.unwrap_or_default();
+        if ids.is_empty() {
+            return Err(ArtError::no_results("Met", format!("no results for '{term}'")));
+        }
+
+        // Try up to 5 random objects to find one with an image. The last error is
+        // kept so a lost connection is reported as one, not as "no results".
+        let mut last_error = None;
+        for _ in 0..5 {
+            let id = ids[rand::rng().random_range(0..ids.len())];
+            let url = format!(
+                "https://collectionapi.metmuseum.org/public/collection/v1/objects/{id}"
+            );
+
+            let result = match get_json::<MetObject>("Met", ctx.client.get(&url)).await {
+                Ok(obj) => Self::object_to_artwork(ctx, obj).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(artwork) => return Ok(artwork),
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| ArtError::no_results("Met", "could not find artwork with image")))
+    }
+
+    async fn fetch_by_id(&self, ctx: &FetchContext, id: &str) -> Result<FetchedArtwork, ArtError> {
+        let url = format!(
+            "https://collectionapi.metmuseum.org/public/collection/v1/objects/{id}"
+        );
+        let obj: MetObject = get_json("Met", ctx.client.get(&url)).await?;
+        Self::object_to_artwork(ctx, obj).await
     }
 
-    Err("Could not find Met artwork with image".into())
+    fn object_url(&self, id: &str) -> Option<String> {
+        Some(format!("https://www.metmuseum.org/art/collection/search/{id}"))
+    }
 }
 
 // ── Art Institute of Chicago API ──
 
+const AIC_FIELDS: &str = "id,title,artist_display,date_display,medium_display,image_id,thumbnail,\
+    credit_line,dimensions,department_title,place_of_origin,main_reference_number,is_public_domain";
+
 #[derive(Deserialize)]
 struct AicSearchResponse {
     #[serde(default)]
@@ -157,6 +799,13 @@ struct AicSearchResponse {
     config: AicConfig,
 }
 
+#[derive(Deserialize)]
+struct AicObjectResponse {
+    data: AicArtwork,
+    #[serde(default)]
+    config: AicConfig,
+}
+
 #[derive(Deserialize, Default)]
 struct AicConfig {
     #[serde(default = "default_iiif_url")]
@@ -175,84 +824,185 @@ struct AicArtwork {
     date_display: Option<String>,
     medium_display: Option<String>,
     image_id: Option<String>,
+    /// Despite the name, `width` and `height` here are those of the full IIIF image
+    thumbnail: Option<AicThumbnail>,
+    credit_line: Option<String>,
+    dimensions: Option<String>,
+    department_title: Option<String>,
+    place_of_origin: Option<String>,
+    main_reference_number: Option<String>,
+    is_public_domain: Option<bool>,
 }
 
-pub async fn fetch_aic_artwork(client: &Client) -> Result<Artwork, String> {
-    let search_terms = [
-        "painting", "landscape", "impressionist", "modern", "watercolor",
-        "oil", "portrait", "nature", "classical", "abstract",
-    ];
-    let term = search_terms[rand::rng().random_range(0..search_terms.len())];
-    let page = rand::rng().random_range(1..=5);
+#[derive(Deserialize)]
+struct AicThumbnail {
+    width: Option<u32>,
+    height: Option<u32>,
+}
 
-    let resp: AicSearchResponse = client
-        .get("https://api.artic.edu/api/v1/artworks/search")
-        .header("AIC-User-Agent", "ArtDisplay/0.1 (Desktop Art Viewer)")
-        .query(&[
-            ("q", term),
-            ("fields", "id,title,artist_display,date_display,medium_display,image_id"),
-            ("limit", "20"),
-            ("page", &page.to_string()),
-        ])
-        .send()
-        .await
-        .map_err(|e| format!("AIC search failed: {e}"))?
-        .json()
-        .await
-        .map_err(|e| format!("AIC parse failed: {e}"))?;
+pub struct AicSource;
+
+impl AicSource {
+    async fn object_to_artwork(
+        ctx: &FetchContext,
+        artwork: &AicArtwork,
+        iiif_url: &str,
+    ) -> Result<FetchedArtwork, ArtError> {
+        let image_id = artwork
+            .image_id
+            .as_ref()
+            .ok_or_else(|| ArtError::no_results("AIC", format!("artwork {} has no image", artwork.id)))?;
 
-    // Shuffle and try artworks until we get a valid image
-    let mut artworks: Vec<&AicArtwork> = resp
-        .data
-        .iter()
-        .filter(|a| a.image_id.is_some())
-        .collect();
+        // IIIF: ask for the monitor's size. AIC only serves 843px for works that
+        // aren't public domain, so fall back to that if the larger size is refused.
+        let image_url = format!(
+            "{iiif_url}/{image_id}/full/{}/0/default.jpg",
+            ctx.target.iiif_fit()
+        );
+        let image = match download_image(&ctx.client, &image_url).await {
+            Ok(image) => image,
+            Err(_) => {
+                let fallback = format!("{iiif_url}/{image_id}/full/843,/0/default.jpg");
+                download_image(&ctx.client, &fallback).await?
+            }
+        };
+        let image_size = artwork.thumbnail.as_ref().and_then(|t| {
+            Some(ImageSize {
+                width: t.width?,
+                height: t.height?,
+            })
+        });
 
-    if artworks.is_empty() {
-        return Err("No AIC artworks with images".into());
+        Ok(FetchedArtwork {
+            artwork: Artwork {
+                id: format!("aic-{}", artwork.id),
+                title: strip_html(&artwork.title.clone().unwrap_or_else(|| "Untitled".into())),
+                artist: artwork
+                    .artist_display
+                    .clone()
+                    .unwrap_or_else(|| "Unknown Artist".into()),
+                date: artwork.date_display.clone().unwrap_or_default(),
+                medium: artwork.medium_display.clone().unwrap_or_default(),
+                source: "Art Institute of Chicago".into(),
+                image_url: String::new(),
+                image_size,
+                credit_line: non_empty(artwork.credit_line.clone()),
+                dimensions: non_empty(artwork.dimensions.clone()),
+                department: non_empty(artwork.department_title.clone()),
+                culture: non_empty(artwork.place_of_origin.clone()),
+                accession_number: non_empty(artwork.main_reference_number.clone()),
+                public_domain: artwork.is_public_domain,
+                license: None,
+                object_url: Some(format!("https://www.artic.edu/artworks/{}", artwork.id)),
+            },
+            image,
+        })
     }
+}
 
-    // Shuffle to avoid always trying the same order
-    use rand::seq::SliceRandom;
-    artworks.shuffle(&mut rand::rng());
+#[async_trait]
+impl ArtSource for AicSource {
+    fn name(&self) -> &str {
+        "AIC"
+    }
 
-    for artwork in artworks.iter().take(5) {
-        let image_id = match &artwork.image_id {
-            Some(id) => id,
-            None => continue,
-        };
+    fn id_prefix(&self) -> &str {
+        "aic"
+    }
 
-        // IIIF: request 843px wide (fast download, plenty for overlay)
-        let image_url = format!(
-            "{}/{}/full/843,/0/default.jpg",
-            resp.config.iiif_url, image_id
-        );
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            fetch_by_id: true,
+            iiif: true,
+            search: true,
+            api_key: false,
+            folder: false,
+        }
+    }
 
-        let (image_bytes, mime) = match download_image(client, &image_url).await {
-            Some(result) => result,
-            None => continue, // try next artwork
-        };
+    fn default_terms(&self) -> &[&'static str] {
+        &[
+            "painting", "landscape", "impressionist", "modern", "watercolor",
+            "oil", "portrait", "nature", "classical", "abstract",
+        ]
+    }
 
-        let b64 = base64::Engine::encode(
-            &base64::engine::general_purpose::STANDARD,
-            &image_bytes,
-        );
+    fn filters(&self) -> &[SourceFilter] {
+        &[SourceFilter {
+            key: "classification_title",
+            label: "classification",
+            numeric: false,
+            default: None,
+        }]
+    }
 
-        return Ok(Artwork {
-            id: format!("aic-{}", artwork.id),
-            title: strip_html(&artwork.title.clone().unwrap_or_else(|| "Untitled".into())),
-            artist: artwork
-                .artist_display
-                .clone()
-                .unwrap_or_else(|| "Unknown Artist".into()),
-            date: artwork.date_display.clone().unwrap_or_default(),
-            medium: artwork.medium_display.clone().unwrap_or_default(),
-            source: "Art Institute of Chicago".into(),
-            image_base64: format!("data:{mime};base64,{b64}"),
-        });
+    async fn fetch_random(&self, ctx: &FetchContext) -> Result<FetchedArtwork, ArtError> {
+        let prefs = ctx.prefs(self.id_prefix());
+        let term = prefs.pick_term(self.default_terms());
+        let page = rand::rng().random_range(1..=5).to_string();
+
+        let mut query = vec![
+            ("q", term),
+            ("fields", AIC_FIELDS),
+            ("limit", "20"),
+            ("page", &page),
+        ];
+        if let Some(classification) = prefs.filter("classification_title") {
+            query.push(("query[match][classification_title]", classification.trim()));
+        }
+        let resp: AicSearchResponse = get_json(
+            "AIC",
+            ctx.client
+                .get("https://api.artic.edu/api/v1/artworks/search")
+                .header("AIC-User-Agent", "ArtDisplay/0.1 (Desktop Art Viewer)")
+                .query(&query),
+        )
+        .await?;
+
+        // Shuffle and try artworks until we get a valid image
+        let mut artworks: Vec<&AicArtwork> = resp
+            .data
+            .iter()
+            .filter(|a| a.image_id.is_some())
+            .collect();
+
+        if artworks.is_empty() {
+            return Err(ArtError::no_results("AIC", format!("no artworks with images for '{term}'")));
+        }
+
+        // Shuffle to avoid always trying the same order
+        use rand::seq::SliceRandom;
+        artworks.shuffle(&mut rand::rng());
+
+        let mut last_error = None;
+        for artwork in artworks.iter().take(5) {
+            match Self::object_to_artwork(ctx, artwork, &resp.config.iiif_url).await {
+                Ok(art) => return Ok(art),
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            ArtError::no_results("AIC", "could not find artwork with valid image")
+        }))
+    }
+
+    async fn fetch_by_id(&self, ctx: &FetchContext, id: &str) -> Result<FetchedArtwork, ArtError> {
+        let resp: AicObjectResponse = get_json(
+            "AIC",
+            ctx.client
+                .get(format!("https://api.artic.edu/api/v1/artworks/{id}"))
+                .header("AIC-User-Agent", "ArtDisplay/0.1 (Desktop Art Viewer)")
+                .query(&[("fields", AIC_FIELDS)]),
+        )
+        .await?;
+
+        Self::object_to_artwork(ctx, &resp.data, &resp.config.iiif_url).await
     }
 
-    Err("Could not find AIC artwork with valid image".into())
+    fn object_url(&self, id: &str) -> Option<String> {
+        Some(format!("https://www.artic.edu/artworks/{id}"))
+    }
 }
 
 // ── Cleveland Museum of Art API ──
@@ -262,6 +1012,11 @@ struct CmaSearchResponse {
     data: Vec<CmaArtwork>,
 }
 
+#[derive(Deserialize)]
+struct CmaObjectResponse {
+    data: CmaArtwork,
+}
+
 #[derive(Deserialize)]
 struct CmaArtwork {
     id: u64,
@@ -271,6 +1026,38 @@ struct CmaArtwork {
     creation_date: Option<String>,
     technique: Option<String>,
     images: Option<CmaImages>,
+    creditline: Option<String>,
+    measurements: Option<String>,
+    department: Option<String>,
+    #[serde(default)]
+    culture: Vec<String>,
+    accession_number: Option<String>,
+    /// "CC0" for public-domain works
+    share_license_status: Option<String>,
+    url: Option<String>,
+}
+
+impl CmaArtwork {
+    fn web_image_url(&self) -> Option<&str> {
+        self.images
+            .as_ref()
+            .and_then(|i| i.web.as_ref())
+            .and_then(|w| w.url.as_deref())
+            .filter(|u| !u.is_empty())
+    }
+
+    /// The web rendition if it covers the target, otherwise the larger print rendition
+    fn image_variant_for(&self, target: ImageSize) -> Option<&CmaImageVariant> {
+        let images = self.images.as_ref()?;
+        let web = images.web.as_ref().filter(|v| v.has_url());
+        let print = images.print.as_ref().filter(|v| v.has_url());
+        match (web, print) {
+            (Some(web), Some(print)) if !web.covers(target) => Some(print),
+            (Some(web), _) => Some(web),
+            (None, Some(print)) => Some(print),
+            (None, None) => None,
+        }
+    }
 }
 
 #[derive(Deserialize)]
@@ -281,75 +1068,59 @@ struct CmaCreator {
 #[derive(Deserialize)]
 struct CmaImages {
     web: Option<CmaImageVariant>,
+    print: Option<CmaImageVariant>,
 }
 
 #[derive(Deserialize)]
 struct CmaImageVariant {
     url: Option<String>,
+    #[serde(default, deserialize_with = "lenient_u32")]
+    width: Option<u32>,
+    #[serde(default, deserialize_with = "lenient_u32")]
+    height: Option<u32>,
 }
 
-pub async fn fetch_cma_artwork(client: &Client) -> Result<Artwork, String> {
-    let search_terms = [
-        "painting", "landscape", "portrait", "impressionist", "modern",
-        "still life", "abstract", "nature", "classical", "oil",
-    ];
-    let term = search_terms[rand::rng().random_range(0..search_terms.len())];
-    let skip = rand::rng().random_range(0..100);
+impl CmaImageVariant {
+    fn has_url(&self) -> bool {
+        self.url.as_deref().is_some_and(|u| !u.is_empty())
+    }
 
-    let resp: CmaSearchResponse = client
-        .get("https://openaccess-api.clevelandart.org/api/artworks/")
-        .query(&[
-            ("q", term),
-            ("has_image", "1"),
-            ("cc0", "1"),
-            ("type", "Painting"),
-            ("limit", "20"),
-            ("skip", &skip.to_string()),
-        ])
-        .send()
-        .await
-        .map_err(|e| format!("CMA search failed: {e}"))?
-        .json()
-        .await
-        .map_err(|e| format!("CMA parse failed: {e}"))?;
-
-    use rand::seq::SliceRandom;
-    let mut artworks: Vec<&CmaArtwork> = resp
-        .data
-        .iter()
-        .filter(|a| {
-            a.images
-                .as_ref()
-                .and_then(|i| i.web.as_ref())
-                .and_then(|w| w.url.as_ref())
-                .map(|u| !u.is_empty())
-                .unwrap_or(false)
+    fn size(&self) -> Option<ImageSize> {
+        Some(ImageSize {
+            width: self.width?,
+            height: self.height?,
         })
-        .collect();
-
-    if artworks.is_empty() {
-        return Err("No CMA artworks with images".into());
     }
 
-    artworks.shuffle(&mut rand::rng());
+    /// Whether this rendition is at least as large as the target on one side
+    /// (enough to fill the screen in a fit-inside layout)
+    fn covers(&self, target: ImageSize) -> bool {
+        match (self.width, self.height) {
+            (Some(w), Some(h)) => w >= target.width || h >= target.height,
+            _ => true,
+        }
+    }
+}
 
-    for artwork in artworks.iter().take(5) {
-        let image_url = artwork
-            .images
-            .as_ref()
-            .and_then(|i| i.web.as_ref())
-            .and_then(|w| w.url.as_ref())
-            .unwrap();
+/// Accept a number given either as a JSON number or a numeric string
+fn lenient_u32<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<u32>, D::Error> {
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(match value {
+        Some(serde_json::Value::Number(n)) => n.as_u64().and_then(|n| u32::try_from(n).ok()),
+        Some(serde_json::Value::String(s)) => s.trim().parse().ok(),
+        _ => None,
+    })
+}
 
-        let (image_bytes, mime) = match download_image(client, image_url).await {
-            Some(result) => result,
-            None => continue,
-        };
+pub struct CmaSource;
 
-        let b64 = base64::Engine::encode(
-            &base64::engine::general_purpose::STANDARD,
-            &image_bytes,
-        );
+impl CmaSource {
+    async fn object_to_artwork(ctx: &FetchContext, artwork: &CmaArtwork) -> Result<FetchedArtwork, ArtError> {
+        let variant = artwork
+            .image_variant_for(ctx.target)
+            .ok_or_else(|| ArtError::no_results("CMA", format!("artwork {} has no image", artwork.id)))?;
+        let image = download_image(&ctx.client, variant.url.as_deref().unwrap_or_default()).await?;
+        let image_size = variant.size();
 
         let artist = artwork
             .creators
@@ -357,22 +1128,135 @@ pub async fn fetch_cma_artwork(client: &Client) -> Result<Artwork, String> {
             .and_then(|c| c.description.clone())
             .unwrap_or_else(|| "Unknown Artist".into());
 
-        return Ok(Artwork {
-            id: format!("cma-{}", artwork.id),
-            title: strip_html(&artwork.title.clone().unwrap_or_else(|| "Untitled".into())),
-            artist,
-            date: artwork.creation_date.clone().unwrap_or_default(),
-            medium: artwork.technique.clone().unwrap_or_default(),
-            source: "Cleveland Museum of Art".into(),
-            image_base64: format!("data:{mime};base64,{b64}"),
-        });
+        Ok(FetchedArtwork {
+            artwork: Artwork {
+                id: format!("cma-{}", artwork.id),
+                title: strip_html(&artwork.title.clone().unwrap_or_else(|| "Untitled".into())),
+                artist,
+                date: artwork.creation_date.clone().unwrap_or_default(),
+                medium: artwork.technique.clone().unwrap_or_default(),
+                source: "Cleveland Museum of Art".into(),
+                image_url: String::new(),
+                image_size,
+                credit_line: non_empty(artwork.creditline.clone()),
+                dimensions: non_empty(artwork.measurements.clone()),
+                department: non_empty(artwork.department.clone()),
+                culture: non_empty(Some(artwork.culture.join(", "))),
+                accession_number: non_empty(artwork.accession_number.clone()),
+                public_domain: artwork
+                    .share_license_status
+                    .as_deref()
+                    .map(|s| s.eq_ignore_ascii_case("CC0")),
+                license: None,
+                object_url: non_empty(artwork.url.clone()),
+            },
+            image,
+        })
+    }
+}
+
+#[async_trait]
+impl ArtSource for CmaSource {
+    fn name(&self) -> &str {
+        "CMA"
+    }
+
+    fn id_prefix(&self) -> &str {
+        "cma"
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            fetch_by_id: true,
+            iiif: false,
+            search: true,
+            api_key: false,
+            folder: false,
+        }
+    }
+
+    fn default_terms(&self) -> &[&'static str] {
+        &[
+            "painting", "landscape", "portrait", "impressionist", "modern",
+            "still life", "abstract", "nature", "classical", "oil",
+        ]
+    }
+
+    fn filters(&self) -> &[SourceFilter] {
+        &[SourceFilter {
+            key: "type",
+            label: "type",
+            numeric: false,
+            default: Some("Painting"),
+        }]
+    }
+
+    async fn fetch_random(&self, ctx: &FetchContext) -> Result<FetchedArtwork, ArtError> {
+        let prefs = ctx.prefs(self.id_prefix());
+        let term = prefs.pick_term(self.default_terms());
+        let kind = prefs.filter("type").map_or("Painting", str::trim);
+        let skip = rand::rng().random_range(0..100);
+
+        let resp: CmaSearchResponse = get_json(
+            "CMA",
+            ctx.client
+                .get("https://openaccess-api.clevelandart.org/api/artworks/")
+                .query(&[
+                    ("q", term),
+                    ("has_image", "1"),
+                    ("cc0", "1"),
+                    ("type", kind),
+                    ("limit", "20"),
+                    ("skip", &skip.to_string()),
+                ]),
+        )
+        .await?;
+
+        use rand::seq::SliceRandom;
+        let mut artworks: Vec<&CmaArtwork> = resp
+            .data
+            .iter()
+            .filter(|a| a.web_image_url().is_some())
+            .collect();
+
+        if artworks.is_empty() {
+            return Err(ArtError::no_results("CMA", format!("no artworks with images for '{term}'")));
+        }
+
+        artworks.shuffle(&mut rand::rng());
+
+        let mut last_error = None;
+        for artwork in artworks.iter().take(5) {
+            match Self::object_to_artwork(ctx, artwork).await {
+                Ok(art) => return Ok(art),
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            ArtError::no_results("CMA", "could not find artwork with valid image")
+        }))
     }
 
-    Err("Could not find CMA artwork with valid image".into())
+    async fn fetch_by_id(&self, ctx: &FetchContext, id: &str) -> Result<FetchedArtwork, ArtError> {
+        let resp: CmaObjectResponse = get_json(
+            "CMA",
+            ctx.client.get(format!("https://openaccess-api.clevelandart.org/api/artworks/{id}")),
+        )
+        .await?;
+
+        Self::object_to_artwork(ctx, &resp.data).await
+    }
+
+    fn object_url(&self, id: &str) -> Option<String> {
+        Some(format!("https://www.clevelandart.org/art/{id}"))
+    }
 }
 
 // ── National Gallery of Art (embedded catalog + IIIF) ──
 
+/// One painting in the embedded catalog (see `scripts/build-nga-catalog.ts`).
+/// The optional fields are missing from catalogs built by older versions of the script.
 #[derive(Deserialize)]
 struct NgaCatalogEntry {
     uuid: String,
@@ -380,6 +1264,28 @@ struct NgaCatalogEntry {
     artist: String,
     date: String,
     medium: String,
+    objectid: Option<String>,
+    accessionnum: Option<String>,
+    creditline: Option<String>,
+    dimensions: Option<String>,
+    department: Option<String>,
+    width: Option<u32>,
+    height: Option<u32>,
+}
+
+impl NgaCatalogEntry {
+    /// Whether the title or artist contains any of the (lowercase) terms
+    fn matches_any(&self, terms: &[String]) -> bool {
+        let title = self.title.to_lowercase();
+        let artist = self.artist.to_lowercase();
+        terms.iter().any(|t| title.contains(t.as_str()) || artist.contains(t.as_str()))
+    }
+
+    /// The object's page, once the catalog records its object id
+    fn object_url(&self) -> Option<String> {
+        let objectid = self.objectid.as_ref()?;
+        Some(format!("https://www.nga.gov/collection/art-object-page.{objectid}.html"))
+    }
 }
 
 static NGA_CATALOG: std::sync::LazyLock<Vec<NgaCatalogEntry>> = std::sync::LazyLock::new(|| {
@@ -387,74 +1293,1469 @@ static NGA_CATALOG: std::sync::LazyLock<Vec<NgaCatalogEntry>> = std::sync::LazyL
     serde_json::from_str(json).expect("Failed to parse embedded NGA catalog")
 });
 
-pub async fn fetch_nga_artwork(client: &Client) -> Result<Artwork, String> {
-    if NGA_CATALOG.is_empty() {
-        return Err("NGA catalog is empty".into());
-    }
-
-    // Try up to 5 random entries
-    for _ in 0..5 {
-        let entry = &NGA_CATALOG[rand::rng().random_range(0..NGA_CATALOG.len())];
+pub struct NgaSource;
 
+impl NgaSource {
+    async fn entry_to_artwork(ctx: &FetchContext, entry: &NgaCatalogEntry) -> Result<FetchedArtwork, ArtError> {
         let image_url = format!(
-            "https://api.nga.gov/iiif/{}/full/!843,843/0/default.jpg",
-            entry.uuid
+            "https://api.nga.gov/iiif/{}/full/{}/0/default.jpg",
+            entry.uuid,
+            ctx.target.iiif_fit()
         );
+        let image = download_image(&ctx.client, &image_url).await?;
+        let image_size = match (entry.width, entry.height) {
+            (Some(width), Some(height)) => Some(ImageSize { width, height }),
+            // Catalogs built before sizes were recorded
+            _ => downloaded_size(&image),
+        };
+
+        Ok(FetchedArtwork {
+            artwork: Artwork {
+                id: format!("nga-{}", entry.uuid),
+                title: entry.title.clone(),
+                artist: entry.artist.clone(),
+                date: entry.date.clone(),
+                medium: entry.medium.clone(),
+                source: "National Gallery of Art".into(),
+                image_url: String::new(),
+                image_size,
+                credit_line: entry.creditline.clone(),
+                dimensions: entry.dimensions.clone(),
+                department: entry.department.clone(),
+                culture: None,
+                accession_number: entry.accessionnum.clone(),
+                // The catalog only holds NGA's open-access images
+                public_domain: Some(true),
+                license: None,
+                object_url: entry.object_url(),
+            },
+            image,
+        })
+    }
+}
+
+#[async_trait]
+impl ArtSource for NgaSource {
+    fn name(&self) -> &str {
+        "NGA"
+    }
+
+    fn id_prefix(&self) -> &str {
+        "nga"
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            fetch_by_id: true,
+            iiif: true,
+            search: true,
+            api_key: false,
+            folder: false,
+        }
+    }
+
+    async fn fetch_random(&self, ctx: &FetchContext) -> Result<FetchedArtwork, ArtError> {
+        // The catalog is embedded, so search terms match titles and artists locally
+        let terms: Vec<String> = ctx
+            .prefs(self.id_prefix())
+            .terms
+            .iter()
+            .map(|t| t.to_lowercase())
+            .collect();
+        let entries: Vec<&NgaCatalogEntry> = NGA_CATALOG
+            .iter()
+            .filter(|e| terms.is_empty() || e.matches_any(&terms))
+            .collect();
+        if entries.is_empty() {
+            return Err(ArtError::no_results("NGA", "no catalog entries match the search terms"));
+        }
+
+        // Try up to 5 random entries
+        let mut last_error = None;
+        for _ in 0..5 {
+            let entry = entries[rand::rng().random_range(0..entries.len())];
+            match Self::entry_to_artwork(ctx, entry).await {
+                Ok(artwork) => return Ok(artwork),
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            ArtError::no_results("NGA", "could not find artwork with valid image")
+        }))
+    }
+
+    async fn fetch_by_id(&self, ctx: &FetchContext, id: &str) -> Result<FetchedArtwork, ArtError> {
+        let entry = NGA_CATALOG
+            .iter()
+            .find(|e| e.uuid == id)
+            .ok_or_else(|| ArtError::no_results("NGA", format!("artwork {id} is not in the catalog")))?;
 
-        let (image_bytes, mime) = match download_image(client, &image_url).await {
-            Some(result) => result,
-            None => continue,
+        Self::entry_to_artwork(ctx, entry).await
+    }
+
+    fn object_url(&self, id: &str) -> Option<String> {
+        NGA_CATALOG.iter().find(|e| e.uuid == id)?.object_url()
+    }
+}
+
+// ── Rijksmuseum API ──
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RijksSearchResponse {
+    #[serde(default)]
+    count: u32,
+    #[serde(default)]
+    art_objects: Vec<RijksSearchItem>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RijksSearchItem {
+    object_number: String,
+    #[serde(default)]
+    has_image: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RijksObjectResponse {
+    art_object: RijksObject,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RijksObject {
+    object_number: String,
+    title: Option<String>,
+    principal_or_first_maker: Option<String>,
+    dating: Option<RijksDating>,
+    physical_medium: Option<String>,
+    /// Dimensions, e.g. "h 379.5cm × w 453.5cm"
+    sub_title: Option<String>,
+    acquisition: Option<RijksAcquisition>,
+    web_image: Option<RijksImage>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RijksDating {
+    presenting_date: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RijksAcquisition {
+    credit_line: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RijksImage {
+    url: String,
+    width: Option<u32>,
+    height: Option<u32>,
+}
+
+impl RijksImage {
+    /// The image scaled down to fit `target`. Images are served from Google's
+    /// image CDN, which takes an `=s<longest side>` suffix.
+    fn url_for(&self, target: ImageSize) -> String {
+        let longest = target.longest_side();
+        // Without a recorded size, ask for the target size rather than risk the full image
+        let size = self.width.zip(self.height);
+        if size.map_or(true, |(w, h)| w.max(h) > longest) {
+            format!("{}=s{longest}", self.url)
+        } else {
+            self.url.clone()
+        }
+    }
+}
+
+/// Objects per search page
+const RIJKS_PAGE_SIZE: u32 = 20;
+
+/// The API refuses pages past the 10,000th result
+const RIJKS_MAX_PAGE: u32 = 10_000 / RIJKS_PAGE_SIZE;
+
+pub struct RijksSource;
+
+impl RijksSource {
+    fn api_key(ctx: &FetchContext) -> Result<String, ArtError> {
+        ctx.prefs("rijks")
+            .api_key()
+            .map(String::from)
+            .ok_or_else(|| ArtError::unsupported("Rijksmuseum needs an API key (see Settings)"))
+    }
+
+    async fn search(
+        ctx: &FetchContext,
+        key: &str,
+        term: &str,
+        kind: &str,
+        page: u32,
+    ) -> Result<RijksSearchResponse, ArtError> {
+        get_json(
+            "Rijksmuseum",
+            ctx.client
+                .get("https://www.rijksmuseum.nl/api/en/collection")
+                .query(&[
+                    ("key", key),
+                    ("q", term),
+                    ("type", kind),
+                    ("imgonly", "true"),
+                    ("ps", &RIJKS_PAGE_SIZE.to_string()),
+                    ("p", &page.to_string()),
+                ]),
+        )
+        .await
+    }
+
+    /// The object's page on the museum website
+    fn page_url(object_number: &str) -> String {
+        format!(
+            "https://www.rijksmuseum.nl/en/collection/{}",
+            percent_encoding::utf8_percent_encode(object_number, percent_encoding::NON_ALPHANUMERIC)
+        )
+    }
+
+    async fn fetch_object(
+        ctx: &FetchContext,
+        key: &str,
+        object_number: &str,
+    ) -> Result<FetchedArtwork, ArtError> {
+        let url = format!(
+            "https://www.rijksmuseum.nl/api/en/collection/{}",
+            percent_encoding::utf8_percent_encode(object_number, percent_encoding::NON_ALPHANUMERIC)
+        );
+        let resp: RijksObjectResponse =
+            get_json("Rijksmuseum", ctx.client.get(url).query(&[("key", key)])).await?;
+        let obj = resp.art_object;
+
+        let web_image = obj.web_image.as_ref().ok_or_else(|| {
+            ArtError::no_results("Rijksmuseum", format!("object {} has no image", obj.object_number))
+        })?;
+        let image = download_image(&ctx.client, &web_image.url_for(ctx.target)).await?;
+        let image_size = match (web_image.width, web_image.height) {
+            (Some(width), Some(height)) => Some(ImageSize { width, height }),
+            _ => downloaded_size(&image),
         };
 
-        let b64 = base64::Engine::encode(
-            &base64::engine::general_purpose::STANDARD,
-            &image_bytes,
+        Ok(FetchedArtwork {
+            artwork: Artwork {
+                id: format!("rijks-{}", obj.object_number),
+                title: obj.title.clone().unwrap_or_else(|| "Untitled".into()),
+                artist: non_empty(obj.principal_or_first_maker.clone())
+                    .unwrap_or_else(|| "Unknown Artist".into()),
+                date: obj.dating.and_then(|d| d.presenting_date).unwrap_or_default(),
+                medium: obj.physical_medium.unwrap_or_default(),
+                source: "Rijksmuseum".into(),
+                image_url: String::new(),
+                image_size,
+                credit_line: non_empty(obj.acquisition.and_then(|a| a.credit_line)),
+                dimensions: non_empty(obj.sub_title),
+                department: None,
+                culture: None,
+                accession_number: Some(obj.object_number.clone()),
+                public_domain: None,
+                license: None,
+                object_url: Some(Self::page_url(&obj.object_number)),
+            },
+            image,
+        })
+    }
+}
+
+#[async_trait]
+impl ArtSource for RijksSource {
+    fn name(&self) -> &str {
+        "Rijksmuseum"
+    }
+
+    fn id_prefix(&self) -> &str {
+        "rijks"
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            fetch_by_id: true,
+            iiif: false,
+            search: true,
+            api_key: true,
+            folder: false,
+        }
+    }
+
+    fn default_terms(&self) -> &[&'static str] {
+        &[
+            "landscape", "portrait", "still life", "seascape", "flowers",
+            "interior", "river", "winter", "city", "mythology",
+        ]
+    }
+
+    fn filters(&self) -> &[SourceFilter] {
+        &[SourceFilter {
+            key: "type",
+            label: "object type",
+            numeric: false,
+            default: Some("painting"),
+        }]
+    }
+
+    async fn fetch_random(&self, ctx: &FetchContext) -> Result<FetchedArtwork, ArtError> {
+        let key = Self::api_key(ctx)?;
+        let prefs = ctx.prefs(self.id_prefix());
+        let term = prefs.pick_term(self.default_terms());
+        let kind = prefs.filter("type").map_or("painting", str::trim);
+
+        let page = rand::rng().random_range(1..=10);
+        let mut resp = Self::search(ctx, &key, term, kind, page).await?;
+        if resp.art_objects.is_empty() && resp.count > 0 {
+            // Guessed past the last page; now we know how many there are
+            let pages = resp.count.div_ceil(RIJKS_PAGE_SIZE).clamp(1, RIJKS_MAX_PAGE);
+            let page = rand::rng().random_range(1..=pages);
+            resp = Self::search(ctx, &key, term, kind, page).await?;
+        }
+
+        use rand::seq::SliceRandom;
+        let mut items: Vec<&RijksSearchItem> =
+            resp.art_objects.iter().filter(|o| o.has_image).collect();
+        if items.is_empty() {
+            return Err(ArtError::no_results(
+                "Rijksmuseum",
+                format!("no artworks with images for '{term}'"),
+            ));
+        }
+        items.shuffle(&mut rand::rng());
+
+        let mut last_error = None;
+        for item in items.iter().take(5) {
+            match Self::fetch_object(ctx, &key, &item.object_number).await {
+                Ok(art) => return Ok(art),
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            ArtError::no_results("Rijksmuseum", "could not find artwork with valid image")
+        }))
+    }
+
+    async fn fetch_by_id(&self, ctx: &FetchContext, id: &str) -> Result<FetchedArtwork, ArtError> {
+        Self::fetch_object(ctx, &Self::api_key(ctx)?, id).await
+    }
+
+    fn object_url(&self, id: &str) -> Option<String> {
+        Some(Self::page_url(id))
+    }
+}
+
+// ── Smithsonian Open Access API ──
+
+/// `s` as a quoted Solr phrase, so quotes, colons or `OR` in user-entered text
+/// can't change the structure of the query
+fn solr_phrase(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[derive(Deserialize)]
+struct SiSearchResponse {
+    response: SiSearchResults,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SiSearchResults {
+    #[serde(default)]
+    row_count: u32,
+    #[serde(default)]
+    rows: Vec<SiRow>,
+}
+
+#[derive(Deserialize)]
+struct SiContentResponse {
+    response: SiRow,
+}
+
+#[derive(Deserialize)]
+struct SiRow {
+    /// EDAN id, e.g. "edanmdm-saam_1929.6.37"
+    id: String,
+    title: Option<String>,
+    content: SiContent,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SiContent {
+    descriptive_non_repeating: SiDescriptive,
+    #[serde(default)]
+    freetext: SiFreetext,
+    #[serde(default)]
+    indexed_structured: SiIndexed,
+}
+
+#[derive(Deserialize)]
+struct SiDescriptive {
+    record_link: Option<String>,
+    data_source: Option<String>,
+    metadata_usage: Option<SiUsage>,
+    online_media: Option<SiOnlineMedia>,
+}
+
+#[derive(Deserialize)]
+struct SiUsage {
+    access: Option<String>,
+}
+
+impl SiUsage {
+    fn is_cc0(usage: &Option<SiUsage>) -> bool {
+        usage
+            .as_ref()
+            .and_then(|u| u.access.as_deref())
+            .is_some_and(|a| a.eq_ignore_ascii_case("CC0"))
+    }
+}
+
+#[derive(Deserialize)]
+struct SiOnlineMedia {
+    #[serde(default)]
+    media: Vec<SiMedia>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SiMedia {
+    #[serde(rename = "type")]
+    media_type: Option<String>,
+    /// Image delivery service id, e.g. "SAAM-1929.6.37_1"
+    ids_id: Option<String>,
+    usage: Option<SiUsage>,
+    #[serde(default)]
+    resources: Vec<SiResource>,
+}
+
+#[derive(Deserialize)]
+struct SiResource {
+    width: Option<u32>,
+    height: Option<u32>,
+}
+
+/// Free-text fields are lists of labelled values, e.g. `{"label": "Medium", "content": "oil"}`
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct SiFreetext {
+    #[serde(default)]
+    name: Vec<SiLabelled>,
+    #[serde(default)]
+    date: Vec<SiLabelled>,
+    #[serde(default)]
+    physical_description: Vec<SiLabelled>,
+    #[serde(default)]
+    credit_line: Vec<SiLabelled>,
+    #[serde(default)]
+    identifier: Vec<SiLabelled>,
+}
+
+#[derive(Deserialize)]
+struct SiLabelled {
+    #[serde(default)]
+    label: String,
+    #[serde(default)]
+    content: String,
+}
+
+/// The first value whose label is one of `labels`, or the first value at all if `labels` is empty
+fn si_value(values: &[SiLabelled], labels: &[&str]) -> Option<String> {
+    let found = if labels.is_empty() {
+        values.first()
+    } else {
+        values
+            .iter()
+            .find(|v| labels.iter().any(|l| v.label.eq_ignore_ascii_case(l)))
+    };
+    non_empty(found.map(|v| v.content.clone()))
+}
+
+#[derive(Deserialize, Default)]
+struct SiIndexed {
+    #[serde(default)]
+    object_type: Vec<String>,
+}
+
+/// Object types that are flat enough to fill a screen
+const SI_2D_TYPES: &[&str] = &["painting", "print", "drawing", "watercolor", "photograph", "poster"];
+
+impl SiRow {
+    fn is_2d(&self) -> bool {
+        self.content.indexed_structured.object_type.iter().any(|t| {
+            let t = t.to_lowercase();
+            SI_2D_TYPES.iter().any(|kind| t.contains(kind))
+        })
+    }
+
+    /// The first CC0 image, if the record itself is CC0
+    fn cc0_image(&self) -> Option<&SiMedia> {
+        let descriptive = &self.content.descriptive_non_repeating;
+        if !SiUsage::is_cc0(&descriptive.metadata_usage) {
+            return None;
+        }
+        descriptive.online_media.as_ref()?.media.iter().find(|m| {
+            m.media_type.as_deref() == Some("Images")
+                && m.ids_id.is_some()
+                && (m.usage.is_none() || SiUsage::is_cc0(&m.usage))
+        })
+    }
+}
+
+pub struct SmithsonianSource;
+
+impl SmithsonianSource {
+    fn api_key(ctx: &FetchContext) -> Result<String, ArtError> {
+        ctx.prefs("si")
+            .api_key()
+            .map(String::from)
+            .ok_or_else(|| ArtError::unsupported("Smithsonian needs an API key (see Settings)"))
+    }
+
+    async fn search(
+        ctx: &FetchContext,
+        key: &str,
+        query: &str,
+        start: u32,
+    ) -> Result<SiSearchResults, ArtError> {
+        let resp: SiSearchResponse = get_json(
+            "Smithsonian",
+            ctx.client
+                .get("https://api.si.edu/openaccess/api/v1.0/category/art_design/search")
+                .header("X-Api-Key", key)
+                .query(&[
+                    ("q", query),
+                    ("rows", "20"),
+                    ("start", &start.to_string()),
+                ]),
+        )
+        .await?;
+        Ok(resp.response)
+    }
+
+    async fn row_to_artwork(ctx: &FetchContext, row: &SiRow) -> Result<FetchedArtwork, ArtError> {
+        let media = row.cc0_image().ok_or_else(|| {
+            ArtError::no_results("Smithsonian", format!("{} has no CC0 image", row.id))
+        })?;
+        let ids_id = media.ids_id.as_deref().unwrap_or_default();
+        let url = format!(
+            "https://ids.si.edu/ids/deliveryService?id={}&max={}",
+            percent_encoding::utf8_percent_encode(ids_id, percent_encoding::NON_ALPHANUMERIC),
+            ctx.target.longest_side()
+        );
+        let image = download_image(&ctx.client, &url).await?;
+
+        // The largest rendition gives the proportions
+        let image_size = media
+            .resources
+            .iter()
+            .filter_map(|r| Some(ImageSize { width: r.width?, height: r.height? }))
+            .max_by_key(|s| s.longest_side());
+
+        let descriptive = &row.content.descriptive_non_repeating;
+        let text = &row.content.freetext;
+        Ok(FetchedArtwork {
+            artwork: Artwork {
+                id: format!("si-{}", row.id),
+                title: non_empty(row.title.clone()).unwrap_or_else(|| "Untitled".into()),
+                artist: si_value(&text.name, &["Artist", "Maker", "Creator", "Photographer"])
+                    .or_else(|| si_value(&text.name, &[]))
+                    .unwrap_or_else(|| "Unknown Artist".into()),
+                date: si_value(&text.date, &[]).unwrap_or_default(),
+                medium: si_value(&text.physical_description, &["Medium", "Physical Description"])
+                    .unwrap_or_default(),
+                source: descriptive
+                    .data_source
+                    .clone()
+                    .unwrap_or_else(|| "Smithsonian".into()),
+                image_url: String::new(),
+                image_size,
+                credit_line: si_value(&text.credit_line, &[]),
+                dimensions: si_value(&text.physical_description, &["Dimensions"]),
+                department: None,
+                culture: None,
+                accession_number: si_value(&text.identifier, &["Object number", "Accession number"]),
+                public_domain: Some(true),
+                license: None,
+                object_url: non_empty(descriptive.record_link.clone()),
+            },
+            image,
+        })
+    }
+}
+
+#[async_trait]
+impl ArtSource for SmithsonianSource {
+    fn name(&self) -> &str {
+        "Smithsonian"
+    }
+
+    fn id_prefix(&self) -> &str {
+        "si"
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            fetch_by_id: true,
+            iiif: false,
+            search: true,
+            api_key: true,
+            folder: false,
+        }
+    }
+
+    fn default_terms(&self) -> &[&'static str] {
+        &[
+            "landscape", "portrait", "still life", "river", "mountains",
+            "flowers", "sea", "city", "garden", "birds",
+        ]
+    }
+
+    fn filters(&self) -> &[SourceFilter] {
+        &[SourceFilter {
+            key: "unit_code",
+            label: "museum code (e.g. SAAM)",
+            numeric: false,
+            default: None,
+        }]
+    }
+
+    async fn fetch_random(&self, ctx: &FetchContext) -> Result<FetchedArtwork, ArtError> {
+        let key = Self::api_key(ctx)?;
+        let prefs = ctx.prefs(self.id_prefix());
+        let term = prefs.pick_term(self.default_terms());
+        // The public domain restriction goes in the query so pages aren't mostly
+        // rows the filter below would drop
+        let mut query = format!(
+            "{} AND online_media_type:\"Images\" AND media_usage:\"CC0\"",
+            solr_phrase(term)
         );
+        if let Some(unit) = prefs.filter("unit_code") {
+            query.push_str(&format!(" AND unit_code:{}", solr_phrase(unit.trim())));
+        }
+
+        let start = rand::rng().random_range(0..200);
+        let mut results = Self::search(ctx, &key, &query, start).await?;
+        if results.rows.is_empty() && results.row_count > 0 {
+            // Guessed past the end; now we know how many there are
+            let start = rand::rng().random_range(0..results.row_count);
+            results = Self::search(ctx, &key, &query, start).await?;
+        }
+
+        use rand::seq::SliceRandom;
+        let mut rows: Vec<&SiRow> = results
+            .rows
+            .iter()
+            .filter(|r| r.is_2d() && r.cc0_image().is_some())
+            .collect();
+        if rows.is_empty() {
+            return Err(ArtError::no_results(
+                "Smithsonian",
+                format!("no CC0 2D artworks with images for '{term}'"),
+            ));
+        }
+        rows.shuffle(&mut rand::rng());
+
+        let mut last_error = None;
+        for row in rows.iter().take(5) {
+            match Self::row_to_artwork(ctx, row).await {
+                Ok(art) => return Ok(art),
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            ArtError::no_results("Smithsonian", "could not find artwork with valid image")
+        }))
+    }
+
+    async fn fetch_by_id(&self, ctx: &FetchContext, id: &str) -> Result<FetchedArtwork, ArtError> {
+        let key = Self::api_key(ctx)?;
+        let resp: SiContentResponse = get_json(
+            "Smithsonian",
+            ctx.client
+                .get(format!("https://api.si.edu/openaccess/api/v1.0/content/{id}"))
+                .header("X-Api-Key", key),
+        )
+        .await?;
+        Self::row_to_artwork(ctx, &resp.response).await
+    }
+
+    fn object_url(&self, id: &str) -> Option<String> {
+        // EDAN ids use a colon on the website: edanmdm:saam_1929.6.37
+        Some(format!(
+            "https://collections.si.edu/search/detail/{}",
+            id.replacen('-', ":", 1)
+        ))
+    }
+}
+
+// ── Harvard Art Museums API ──
+
+const HARVARD_FIELDS: &str = "id,title,people,dated,medium,creditline,dimensions,department,\
+    culture,objectnumber,url,primaryimageurl,images,imagepermissionlevel";
+
+#[derive(Deserialize)]
+struct HarvardSearchResponse {
+    #[serde(default)]
+    records: Vec<HarvardObject>,
+}
+
+#[derive(Deserialize)]
+struct HarvardObject {
+    id: u64,
+    title: Option<String>,
+    #[serde(default)]
+    people: Vec<HarvardPerson>,
+    dated: Option<String>,
+    medium: Option<String>,
+    creditline: Option<String>,
+    dimensions: Option<String>,
+    department: Option<String>,
+    culture: Option<String>,
+    objectnumber: Option<String>,
+    url: Option<String>,
+    primaryimageurl: Option<String>,
+    #[serde(default)]
+    images: Vec<HarvardImage>,
+    /// 0 means the image may be shown at any size
+    imagepermissionlevel: Option<u8>,
+}
+
+#[derive(Deserialize)]
+struct HarvardPerson {
+    name: Option<String>,
+    role: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct HarvardImage {
+    baseimageurl: Option<String>,
+    iiifbaseuri: Option<String>,
+    width: Option<u32>,
+    height: Option<u32>,
+}
+
+impl HarvardObject {
+    /// The record's primary image, looked up in `images` for its IIIF base and size
+    fn primary_image(&self) -> Option<&HarvardImage> {
+        let primary = self.primaryimageurl.as_deref()?;
+        self.images
+            .iter()
+            .find(|i| i.baseimageurl.as_deref() == Some(primary))
+            .or_else(|| self.images.first())
+    }
+
+    fn image_url_for(&self, target: ImageSize) -> Option<String> {
+        if self.imagepermissionlevel != Some(0) {
+            return None;
+        }
+        let primary = self.primaryimageurl.as_deref()?;
+        match self.primary_image().and_then(|i| i.iiifbaseuri.as_deref()) {
+            Some(iiif) => Some(format!("{iiif}/full/{}/0/default.jpg", target.iiif_fit())),
+            // Older records have no IIIF base; the image service still takes a width
+            None => Some(format!("{primary}?width={}", target.width)),
+        }
+    }
+
+    fn artist(&self) -> Option<String> {
+        self.people
+            .iter()
+            .find(|p| p.role.as_deref() == Some("Artist"))
+            .or_else(|| self.people.first())
+            .and_then(|p| non_empty(p.name.clone()))
+    }
+}
+
+pub struct HarvardSource;
 
-        return Ok(Artwork {
-            id: format!("nga-{}", entry.uuid),
-            title: entry.title.clone(),
-            artist: entry.artist.clone(),
-            date: entry.date.clone(),
-            medium: entry.medium.clone(),
-            source: "National Gallery of Art".into(),
-            image_base64: format!("data:{mime};base64,{b64}"),
+impl HarvardSource {
+    fn api_key(ctx: &FetchContext) -> Result<String, ArtError> {
+        ctx.prefs("harvard")
+            .api_key()
+            .map(String::from)
+            .ok_or_else(|| ArtError::unsupported("Harvard Art Museums needs an API key (see Settings)"))
+    }
+
+    async fn object_to_artwork(ctx: &FetchContext, obj: &HarvardObject) -> Result<FetchedArtwork, ArtError> {
+        let url = obj.image_url_for(ctx.target).ok_or_else(|| {
+            ArtError::no_results("Harvard", format!("object {} has no displayable image", obj.id))
+        })?;
+        let image = download_image(&ctx.client, &url).await?;
+        let image_size = obj.primary_image().and_then(|i| {
+            Some(ImageSize {
+                width: i.width?,
+                height: i.height?,
+            })
         });
+
+        Ok(FetchedArtwork {
+            artwork: Artwork {
+                id: format!("harvard-{}", obj.id),
+                title: non_empty(obj.title.clone()).unwrap_or_else(|| "Untitled".into()),
+                artist: obj.artist().unwrap_or_else(|| "Unknown Artist".into()),
+                date: obj.dated.clone().unwrap_or_default(),
+                medium: obj.medium.clone().unwrap_or_default(),
+                source: "Harvard Art Museums".into(),
+                image_url: String::new(),
+                image_size,
+                credit_line: non_empty(obj.creditline.clone()),
+                dimensions: non_empty(obj.dimensions.clone()),
+                department: non_empty(obj.department.clone()),
+                culture: non_empty(obj.culture.clone()),
+                accession_number: non_empty(obj.objectnumber.clone()),
+                public_domain: None,
+                license: None,
+                object_url: non_empty(obj.url.clone()),
+            },
+            image,
+        })
+    }
+}
+
+#[async_trait]
+impl ArtSource for HarvardSource {
+    fn name(&self) -> &str {
+        "Harvard"
+    }
+
+    fn id_prefix(&self) -> &str {
+        "harvard"
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            fetch_by_id: true,
+            iiif: true,
+            search: true,
+            api_key: true,
+            folder: false,
+        }
+    }
+
+    fn default_terms(&self) -> &[&'static str] {
+        &[
+            "landscape", "portrait", "still life", "garden", "river",
+            "flowers", "sea", "village", "mountain", "figure",
+        ]
+    }
+
+    fn filters(&self) -> &[SourceFilter] {
+        &[SourceFilter {
+            key: "classification",
+            label: "classification",
+            numeric: false,
+            default: Some("Paintings"),
+        }]
+    }
+
+    async fn fetch_random(&self, ctx: &FetchContext) -> Result<FetchedArtwork, ArtError> {
+        let key = Self::api_key(ctx)?;
+        let prefs = ctx.prefs(self.id_prefix());
+        let term = prefs.pick_term(self.default_terms());
+        let classification = prefs.filter("classification").map_or("Paintings", str::trim);
+
+        let resp: HarvardSearchResponse = get_json(
+            "Harvard",
+            ctx.client
+                .get("https://api.harvardartmuseums.org/object")
+                .query(&[
+                    ("apikey", key.as_str()),
+                    ("hasimage", "1"),
+                    ("sort", "random"),
+                    ("keyword", term),
+                    ("classification", classification),
+                    ("fields", HARVARD_FIELDS),
+                    ("size", "20"),
+                ]),
+        )
+        .await?;
+
+        // sort=random already shuffled them
+        let objects: Vec<&HarvardObject> = resp
+            .records
+            .iter()
+            .filter(|o| o.image_url_for(ctx.target).is_some())
+            .collect();
+        if objects.is_empty() {
+            return Err(ArtError::no_results("Harvard", format!("no artworks with images for '{term}'")));
+        }
+
+        let mut last_error = None;
+        for obj in objects.iter().take(5) {
+            match Self::object_to_artwork(ctx, obj).await {
+                Ok(art) => return Ok(art),
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            ArtError::no_results("Harvard", "could not find artwork with valid image")
+        }))
     }
 
-    Err("Could not find NGA artwork with valid image".into())
+    async fn fetch_by_id(&self, ctx: &FetchContext, id: &str) -> Result<FetchedArtwork, ArtError> {
+        let key = Self::api_key(ctx)?;
+        let obj: HarvardObject = get_json(
+            "Harvard",
+            ctx.client
+                .get(format!("https://api.harvardartmuseums.org/object/{id}"))
+                .query(&[("apikey", key.as_str()), ("fields", HARVARD_FIELDS)]),
+        )
+        .await?;
+        Self::object_to_artwork(ctx, &obj).await
+    }
+
+    fn object_url(&self, id: &str) -> Option<String> {
+        Some(format!("https://harvardartmuseums.org/collections/object/{id}"))
+    }
 }
 
-/// Fetch a random artwork from any source
-pub async fn fetch_random_artwork(client: &Client) -> Result<Artwork, String> {
-    // Pick a random source (0=Met, 1=AIC, 2=CMA, 3=NGA)
-    let source = rand::rng().random_range(0..4u32);
+// ── Wikimedia Commons (MediaWiki API) ──
 
-    let fetchers: [fn(&Client) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Artwork, String>> + Send + '_>>; 4] = [
-        |c| Box::pin(fetch_met_artwork(c)),
-        |c| Box::pin(fetch_aic_artwork(c)),
-        |c| Box::pin(fetch_cma_artwork(c)),
-        |c| Box::pin(fetch_nga_artwork(c)),
-    ];
+const COMMONS_API: &str = "https://commons.wikimedia.org/w/api.php";
 
-    let names = ["Met", "AIC", "CMA", "NGA"];
+const COMMONS_DEFAULT_CATEGORY: &str = "Featured pictures of paintings";
 
-    // Try the selected source first, then fall back to others
-    let order: Vec<usize> = {
-        let start = source as usize;
-        (0..4).map(|i| (start + i) % 4).collect()
-    };
+/// The extmetadata fields we read; asking for fewer keeps responses small
+const COMMONS_METADATA: &str = "ObjectName|Artist|DateTimeOriginal|LicenseShortName|Copyrighted|Credit";
+
+#[derive(Deserialize)]
+struct CommonsResponse {
+    query: Option<CommonsQuery>,
+}
+
+#[derive(Deserialize)]
+struct CommonsQuery {
+    #[serde(default)]
+    pages: Vec<CommonsPage>,
+}
+
+#[derive(Deserialize)]
+struct CommonsPage {
+    pageid: Option<u64>,
+    title: String,
+    #[serde(default)]
+    imageinfo: Vec<CommonsImageInfo>,
+}
+
+#[derive(Deserialize)]
+struct CommonsImageInfo {
+    width: u32,
+    height: u32,
+    mime: Option<String>,
+    /// Scaled to `iiurlwidth`; the original when it's no wider than that
+    thumburl: Option<String>,
+    descriptionurl: Option<String>,
+    #[serde(default)]
+    extmetadata: BTreeMap<String, CommonsMetadataValue>,
+}
+
+#[derive(Deserialize)]
+struct CommonsMetadataValue {
+    value: serde_json::Value,
+}
+
+impl CommonsImageInfo {
+    /// An extmetadata field as plain text; values are often HTML
+    fn metadata(&self, key: &str) -> Option<String> {
+        let value = self.extmetadata.get(key)?;
+        let text = match &value.value {
+            serde_json::Value::String(s) => strip_html(s),
+            other => other.to_string(),
+        };
+        non_empty(Some(text))
+    }
+
+    /// Bitmap images only; SVGs, PDFs and videos don't make good wallpaper
+    fn is_picture(&self) -> bool {
+        self.thumburl.is_some()
+            && self
+                .mime
+                .as_deref()
+                .is_some_and(|m| matches!(m, "image/jpeg" | "image/png" | "image/tiff" | "image/webp"))
+    }
+}
+
+pub struct CommonsSource;
+
+impl CommonsSource {
+    /// Query parameters asking for image info and metadata, scaled for `target`
+    fn imageinfo_params(target: ImageSize) -> Vec<(&'static str, String)> {
+        vec![
+            ("action", "query".into()),
+            ("format", "json".into()),
+            ("formatversion", "2".into()),
+            ("prop", "imageinfo".into()),
+            ("iiprop", "url|size|mime|extmetadata".into()),
+            // Sizes aren't known until the response, so scale to the target width
+            ("iiurlwidth", target.width.to_string()),
+            ("iiextmetadatafilter", COMMONS_METADATA.into()),
+            ("iiextmetadatalanguage", "en".into()),
+        ]
+    }
 
-    let mut last_err = String::new();
-    for &idx in &order {
-        match fetchers[idx](client).await {
-            Ok(art) => return Ok(art),
+    async fn page_to_artwork(ctx: &FetchContext, page: &CommonsPage) -> Result<FetchedArtwork, ArtError> {
+        let pageid = page
+            .pageid
+            .ok_or_else(|| ArtError::no_results("Commons", format!("{} does not exist", page.title)))?;
+        let info = page
+            .imageinfo
+            .first()
+            .filter(|i| i.is_picture())
+            .ok_or_else(|| ArtError::no_results("Commons", format!("{} is not a picture", page.title)))?;
+        let image = download_image(&ctx.client, info.thumburl.as_deref().unwrap_or_default()).await?;
+
+        // "File:Rembrandt - The Night Watch.jpg" when there's no object name
+        let file_title = page
+            .title
+            .trim_start_matches("File:")
+            .rsplit_once('.')
+            .map_or(page.title.as_str(), |(name, _)| name);
+        let license = info.metadata("LicenseShortName");
+        let public_domain = license.as_deref().map(|l| {
+            let l = l.to_lowercase();
+            l.contains("public domain") || l == "pd" || l.starts_with("pd-") || l.starts_with("cc0")
+        });
+
+        Ok(FetchedArtwork {
+            artwork: Artwork {
+                id: format!("commons-{pageid}"),
+                title: info.metadata("ObjectName").unwrap_or_else(|| file_title.into()),
+                artist: info.metadata("Artist").unwrap_or_else(|| "Unknown Artist".into()),
+                date: info.metadata("DateTimeOriginal").unwrap_or_default(),
+                medium: String::new(),
+                source: "Wikimedia Commons".into(),
+                image_url: String::new(),
+                image_size: Some(ImageSize {
+                    width: info.width,
+                    height: info.height,
+                }),
+                credit_line: info.metadata("Credit"),
+                dimensions: None,
+                department: None,
+                culture: None,
+                accession_number: None,
+                public_domain,
+                license,
+                object_url: non_empty(info.descriptionurl.clone()),
+            },
+            image,
+        })
+    }
+
+    async fn query(ctx: &FetchContext, params: &[(&str, String)]) -> Result<Vec<CommonsPage>, ArtError> {
+        let resp: CommonsResponse =
+            get_json("Commons", ctx.client.get(COMMONS_API).query(params)).await?;
+        Ok(resp.query.map(|q| q.pages).unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl ArtSource for CommonsSource {
+    fn name(&self) -> &str {
+        "Commons"
+    }
+
+    fn id_prefix(&self) -> &str {
+        "commons"
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            fetch_by_id: true,
+            iiif: false,
+            search: false,
+            api_key: false,
+            folder: false,
+        }
+    }
+
+    fn filters(&self) -> &[SourceFilter] {
+        &[SourceFilter {
+            key: "category",
+            label: "category",
+            numeric: false,
+            default: Some(COMMONS_DEFAULT_CATEGORY),
+        }]
+    }
+
+    async fn fetch_random(&self, ctx: &FetchContext) -> Result<FetchedArtwork, ArtError> {
+        let prefs = ctx.prefs(self.id_prefix());
+        let category = prefs.filter("category").map_or(COMMONS_DEFAULT_CATEGORY, str::trim);
+        let category = category.strip_prefix("Category:").unwrap_or(category);
+
+        // Categories can't be sampled directly, so start listing at a random
+        // point in time the files were added
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        // Commons opened in September 2004
+        let start = rand::rng().random_range(1_095_000_000..now.max(1_095_000_001));
+
+        let mut params = Self::imageinfo_params(ctx.target);
+        params.extend([
+            ("generator", "categorymembers".into()),
+            ("gcmtitle", format!("Category:{category}")),
+            ("gcmtype", "file".into()),
+            ("gcmsort", "timestamp".into()),
+            ("gcmlimit", "20".into()),
+        ]);
+        let mut from_start = params.clone();
+        from_start.push(("gcmstart", start.to_string()));
+        let mut pages = Self::query(ctx, &from_start).await?;
+        if pages.is_empty() {
+            // Started after the newest file; list from the beginning instead
+            pages = Self::query(ctx, &params).await?;
+        }
+
+        use rand::seq::SliceRandom;
+        let mut pages: Vec<&CommonsPage> = pages
+            .iter()
+            .filter(|p| p.imageinfo.first().is_some_and(CommonsImageInfo::is_picture))
+            .collect();
+        if pages.is_empty() {
+            return Err(ArtError::no_results("Commons", format!("no pictures in Category:{category}")));
+        }
+        pages.shuffle(&mut rand::rng());
+
+        let mut last_error = None;
+        for page in pages.iter().take(5) {
+            match Self::page_to_artwork(ctx, page).await {
+                Ok(art) => return Ok(art),
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            ArtError::no_results("Commons", "could not find artwork with valid image")
+        }))
+    }
+
+    async fn fetch_by_id(&self, ctx: &FetchContext, id: &str) -> Result<FetchedArtwork, ArtError> {
+        let mut params = Self::imageinfo_params(ctx.target);
+        params.push(("pageids", id.into()));
+        let pages = Self::query(ctx, &params).await?;
+        let page = pages
+            .first()
+            .ok_or_else(|| ArtError::no_results("Commons", format!("no file with page id {id}")))?;
+        Self::page_to_artwork(ctx, page).await
+    }
+
+    fn object_url(&self, id: &str) -> Option<String> {
+        Some(format!("https://commons.wikimedia.org/?curid={id}"))
+    }
+}
+
+// ── Local folder ──
+
+/// Image types the overlay can display
+const LOCAL_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "webp", "gif"];
+
+fn mime_for_extension(ext: &str) -> &'static str {
+    match ext {
+        "png" => "image/png",
+        "webp" => "image/webp",
+        "gif" => "image/gif",
+        _ => "image/jpeg",
+    }
+}
+
+/// Metadata kept next to an image as `<name>.json` (the format `save_artwork` writes)
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct LocalSidecar {
+    title: Option<String>,
+    artist: Option<String>,
+    date: Option<String>,
+    medium: Option<String>,
+    credit_line: Option<String>,
+    license: Option<String>,
+}
+
+/// Title, artist and date embedded in the image file itself
+#[derive(Default)]
+struct EmbeddedMetadata {
+    title: Option<String>,
+    artist: Option<String>,
+    date: Option<String>,
+    size: Option<ImageSize>,
+}
+
+impl EmbeddedMetadata {
+    /// XMP takes precedence over EXIF, as editors update it first
+    fn read(bytes: &[u8]) -> Self {
+        let mut meta = Self::from_exif(bytes);
+        if let Some(xmp) = xmp_packet(bytes) {
+            meta.title = xmp_value(xmp, "dc:title").or(meta.title);
+            meta.artist = xmp_value(xmp, "dc:creator").or(meta.artist);
+        }
+        meta
+    }
+
+    fn from_exif(bytes: &[u8]) -> Self {
+        use exif::{In, Tag, Value};
+
+        let Ok(exif) = exif::Reader::new().read_from_container(&mut std::io::Cursor::new(bytes)) else {
+            return Self::default();
+        };
+        let text = |tag| match exif.get_field(tag, In::PRIMARY).map(|f| &f.value) {
+            Some(Value::Ascii(parts)) => {
+                non_empty(parts.first().map(|p| String::from_utf8_lossy(p).into_owned()))
+            }
+            _ => None,
+        };
+        let number = |tag| exif.get_field(tag, In::PRIMARY)?.value.get_uint(0);
+
+        Self {
+            title: text(Tag::ImageDescription),
+            artist: text(Tag::Artist),
+            // "2021:05:03 10:00:00" -> "2021-05-03"
+            date: text(Tag::DateTimeOriginal)
+                .and_then(|d| d.get(..10).map(|d| d.replace(':', "-"))),
+            size: number(Tag::PixelXDimension)
+                .zip(number(Tag::PixelYDimension))
+                .map(|(width, height)| ImageSize { width, height }),
+        }
+    }
+}
+
+/// The XMP packet embedded in an image, if any
+fn xmp_packet(bytes: &[u8]) -> Option<&str> {
+    const START: &[u8] = b"<x:xmpmeta";
+    const END: &[u8] = b"</x:xmpmeta>";
+    let start = bytes.windows(START.len()).position(|w| w == START)?;
+    let len = bytes[start..].windows(END.len()).position(|w| w == END)?;
+    std::str::from_utf8(&bytes[start..start + len]).ok()
+}
+
+/// First `rdf:li` inside an XMP property such as `dc:title` (a language
+/// alternative) or `dc:creator` (an ordered list)
+fn xmp_value(xmp: &str, property: &str) -> Option<String> {
+    let block = &xmp[xmp.find(&format!("<{property}>"))?..];
+    let block = &block[..block.find(&format!("</{property}>"))?];
+    let item = &block[block.find("<rdf:li")?..];
+    let item = &item[item.find('>')? + 1..];
+    let value = &item[..item.find("</rdf:li>")?];
+    let value = value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&");
+    non_empty(Some(value))
+}
+
+/// Relative paths of the images under a folder, recursively, skipping hidden files
+fn scan_folder(folder: &Path) -> Vec<PathBuf> {
+    walkdir::WalkDir::new(folder)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            e.path()
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| LOCAL_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+        })
+        .filter_map(|e| e.path().strip_prefix(folder).ok().map(Path::to_path_buf))
+        .collect()
+}
+
+/// The images in the configured folder, rescanned whenever the watcher sees a change
+#[derive(Default)]
+struct LocalIndex {
+    folder: Option<PathBuf>,
+    files: Vec<PathBuf>,
+    /// Set by the watcher when anything under `folder` changes
+    stale: Arc<AtomicBool>,
+    watcher: Option<notify::RecommendedWatcher>,
+}
+
+impl LocalIndex {
+    /// Make sure the index covers `folder` and is up to date
+    async fn refresh(&mut self, folder: &Path) {
+        if self.folder.as_deref() != Some(folder) {
+            self.watcher = Self::watch(folder, self.stale.clone());
+            self.folder = Some(folder.to_path_buf());
+            self.stale.store(true, Ordering::Relaxed);
+        }
+        // Without a watcher there's no telling when files change, so rescan each time
+        if self.stale.swap(false, Ordering::Relaxed) || self.watcher.is_none() {
+            let dir = folder.to_path_buf();
+            self.files = tokio::task::spawn_blocking(move || scan_folder(&dir))
+                .await
+                .unwrap_or_default();
+            log::info!("Found {} images in {}", self.files.len(), folder.display());
+        }
+    }
+
+    fn watch(folder: &Path, stale: Arc<AtomicBool>) -> Option<notify::RecommendedWatcher> {
+        use notify::Watcher;
+
+        let result = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            if event.is_ok_and(|e| !e.kind.is_access()) {
+                stale.store(true, Ordering::Relaxed);
+            }
+        })
+        .and_then(|mut watcher| {
+            watcher.watch(folder, notify::RecursiveMode::Recursive)?;
+            Ok(watcher)
+        });
+        match result {
+            Ok(watcher) => Some(watcher),
             Err(e) => {
-                log::warn!("{} failed: {e}", names[idx]);
-                last_err = e;
+                log::warn!("Can't watch {} for changes: {e}", folder.display());
+                None
+            }
+        }
+    }
+}
+
+/// Images from a folder on disk, e.g. a shared drive of the team's own work
+#[derive(Default)]
+pub struct LocalFolderSource {
+    index: tokio::sync::Mutex<LocalIndex>,
+}
+
+impl LocalFolderSource {
+    fn folder(ctx: &FetchContext) -> Result<PathBuf, ArtError> {
+        ctx.prefs("local")
+            .folder()
+            .ok_or_else(|| ArtError::unsupported("Choose a folder for local images in Settings"))
+    }
+
+    async fn load(folder: &Path, relative: &Path) -> Result<FetchedArtwork, ArtError> {
+        let path = folder.join(relative);
+        let bytes = tokio::fs::read(&path)
+            .await
+            .map_err(|e| ArtError::no_results("Local", format!("can't read {}: {e}", path.display())))?;
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        let sidecar = Self::read_sidecar(&path).await.unwrap_or_default();
+        let embedded = EmbeddedMetadata::read(&bytes);
+
+        // "Artist - Title.jpg", as save_artwork names files, or just "Title.jpg"
+        let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+        let (name_artist, name_title) = match stem.split_once(" - ") {
+            Some((artist, title)) => (Some(artist.trim().to_string()), title.trim().to_string()),
+            None => (None, stem.replace('_', " ")),
+        };
+
+        // Ids use forward slashes on every platform
+        let id = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        Ok(FetchedArtwork {
+            artwork: Artwork {
+                id: format!("local-{id}"),
+                title: non_empty(sidecar.title)
+                    .or(embedded.title)
+                    .unwrap_or(name_title),
+                artist: non_empty(sidecar.artist)
+                    .or(embedded.artist)
+                    .or(name_artist)
+                    .unwrap_or_else(|| "Unknown Artist".into()),
+                date: non_empty(sidecar.date).or(embedded.date).unwrap_or_default(),
+                medium: sidecar.medium.unwrap_or_default(),
+                source: "Local folder".into(),
+                image_url: String::new(),
+                image_size: embedded.size,
+                credit_line: non_empty(sidecar.credit_line),
+                dimensions: None,
+                department: None,
+                culture: None,
+                accession_number: None,
+                public_domain: None,
+                license: non_empty(sidecar.license),
+                // Opens the file in the default image viewer
+                object_url: tauri::Url::from_file_path(&path).ok().map(String::from),
+            },
+            image: ImageData {
+                bytes,
+                mime: mime_for_extension(&ext).into(),
+            },
+        })
+    }
+
+    /// `photo.json` or `photo.jpg.json` next to `photo.jpg`
+    async fn read_sidecar(path: &Path) -> Option<LocalSidecar> {
+        let mut with_ext = path.as_os_str().to_owned();
+        with_ext.push(".json");
+        for candidate in [path.with_extension("json"), PathBuf::from(with_ext)] {
+            if let Ok(json) = tokio::fs::read(&candidate).await {
+                match serde_json::from_slice(&json) {
+                    Ok(sidecar) => return Some(sidecar),
+                    Err(e) => log::warn!("Ignoring {}: {e}", candidate.display()),
+                }
             }
         }
+        None
+    }
+}
+
+#[async_trait]
+impl ArtSource for LocalFolderSource {
+    fn name(&self) -> &str {
+        "Local folder"
+    }
+
+    fn id_prefix(&self) -> &str {
+        "local"
     }
 
-    Err(format!("All sources failed. Last error: {last_err}"))
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            fetch_by_id: true,
+            iiif: false,
+            search: false,
+            api_key: false,
+            folder: true,
+        }
+    }
+
+    async fn fetch_random(&self, ctx: &FetchContext) -> Result<FetchedArtwork, ArtError> {
+        let folder = Self::folder(ctx)?;
+        let candidates: Vec<PathBuf> = {
+            let mut index = self.index.lock().await;
+            index.refresh(&folder).await;
+            let mut rng = rand::rng();
+            index.files.choose_multiple(&mut rng, 5).cloned().collect()
+        };
+        if candidates.is_empty() {
+            return Err(ArtError::no_results("Local", format!("no images in {}", folder.display())));
+        }
+
+        for relative in &candidates {
+            match Self::load(&folder, relative).await {
+                Ok(art) => return Ok(art),
+                Err(e) => log::warn!("{e}"),
+            }
+        }
+
+        Err(ArtError::no_results("Local", "could not read any images"))
+    }
+
+    async fn fetch_by_id(&self, ctx: &FetchContext, id: &str) -> Result<FetchedArtwork, ArtError> {
+        let relative = Path::new(id);
+        // Ids come from our own history, but never read outside the folder
+        if !relative.components().all(|c| matches!(c, std::path::Component::Normal(_))) {
+            return Err(ArtError::unsupported(format!("Invalid local image id: {id}")));
+        }
+        Self::load(&Self::folder(ctx)?, relative).await
+    }
 }
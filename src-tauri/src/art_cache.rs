@@ -1,138 +1,588 @@
-use crate::art_api::fetch_random_artwork;
+use crate::art_api::{FetchContext, FetchedArtwork, ImageData, ImageSize, SourceRegistry, SourceSettings};
+use crate::disk_cache::DiskCache;
+use crate::error::ArtError;
+use crate::favorites::Favorites;
 use crate::Artwork;
 use reqwest::Client;
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 const CACHE_SIZE: usize = 5;
 
+/// How many recently shown artworks offline and favorites rotation avoid repeating
+const RECENT_WINDOW: usize = 20;
+
+/// Prefetch retry delay while offline, so we don't hammer a dead connection
+const OFFLINE_RETRY_SECS: u64 = 30;
+
+/// Fit score given to artwork whose size the source didn't report: ln(1.5), the
+/// score of a 1.5x aspect mismatch. Closer matches beat it, while a portrait on a
+/// landscape screen (usually 2x or more off) loses to it.
+const UNKNOWN_FIT: f64 = 0.405;
+
+/// How badly an artwork's aspect ratio matches the screen; 0 is a perfect fit
+fn aspect_mismatch(artwork: &Artwork, screen: ImageSize) -> f64 {
+    match artwork.image_size {
+        Some(size) => (size.aspect_ratio() / screen.aspect_ratio()).ln().abs(),
+        None => UNKNOWN_FIT,
+    }
+}
+
+/// History key used when every monitor shows the same artwork
+pub const SHARED_HISTORY: &str = "shared";
+
+/// Artwork shown in one place (all monitors, or a single overlay window), with a
+/// cursor for browsing back and forth
+#[derive(Default)]
+struct History {
+    items: Vec<Artwork>,
+    /// `Some` while browsing back through history, `None` when at the newest item
+    index: Option<usize>,
+}
+
+impl History {
+    /// Move forward if we're browsing history; `None` means new artwork is needed
+    fn forward(&mut self) -> Option<Artwork> {
+        let i = self.index?;
+        if i + 1 < self.items.len() {
+            self.index = Some(i + 1);
+            Some(self.items[i + 1].clone())
+        } else {
+            // At end of history, fall through to get new artwork
+            self.index = None;
+            None
+        }
+    }
+
+    fn back(&mut self) -> Result<Artwork, ArtError> {
+        if self.items.is_empty() {
+            return Err(ArtError::history("No history"));
+        }
+
+        let new_idx = match self.index {
+            Some(0) => return Err(ArtError::history("At beginning of history")),
+            Some(i) => i - 1,
+            None => {
+                // Start browsing from the last item
+                if self.items.len() >= 2 {
+                    self.items.len() - 2
+                } else {
+                    return Err(ArtError::history("No previous artwork"));
+                }
+            }
+        };
+
+        self.index = Some(new_idx);
+        Ok(self.items[new_idx].clone())
+    }
+
+    fn push(&mut self, artwork: Artwork) {
+        self.items.push(artwork);
+        self.index = None;
+        // Keep history reasonable
+        if self.items.len() > 50 {
+            self.items.drain(0..25);
+        }
+    }
+
+    fn current(&self) -> Option<Artwork> {
+        match self.index {
+            Some(i) => self.items.get(i).cloned(),
+            None => self.items.last().cloned(),
+        }
+    }
+}
+
 pub struct ArtCache {
     client: Client,
+    /// Size to download images at, tracking the largest connected monitor
+    target_size: Arc<std::sync::Mutex<ImageSize>>,
+    sources: Arc<SourceRegistry>,
+    /// Which sources to draw from and how often
+    source_settings: Arc<std::sync::Mutex<Arc<SourceSettings>>>,
+    disk: Arc<DiskCache>,
+    favorites: Option<Arc<Favorites>>,
+    /// Rotate through favorites instead of fetching new artwork
+    favorites_only: AtomicBool,
     cache: Arc<Mutex<VecDeque<Artwork>>>,
-    history: Arc<Mutex<Vec<Artwork>>>,
-    history_index: Arc<Mutex<Option<usize>>>,
+    /// One history per key: `SHARED_HISTORY`, or an overlay window label
+    histories: Mutex<HashMap<String, History>>,
+    /// Set when fetches fail for connectivity reasons; cleared by the next successful prefetch
+    offline: Arc<AtomicBool>,
 }
 
 impl ArtCache {
-    pub fn new() -> Self {
+    pub fn new(
+        sources: SourceRegistry,
+        disk: DiskCache,
+        favorites: Option<Favorites>,
+    ) -> Self {
         Self {
             client: Client::builder()
                 .user_agent("ArtDisplay/0.1 (Desktop Art Viewer)")
+                .connect_timeout(std::time::Duration::from_secs(10))
+                .timeout(std::time::Duration::from_secs(60))
                 .build()
                 .unwrap_or_default(),
+            target_size: Arc::new(std::sync::Mutex::new(ImageSize::DEFAULT)),
+            sources: Arc::new(sources),
+            source_settings: Arc::default(),
+            disk: Arc::new(disk),
+            favorites: favorites.map(Arc::new),
+            favorites_only: AtomicBool::new(false),
             cache: Arc::new(Mutex::new(VecDeque::new())),
-            history: Arc::new(Mutex::new(Vec::new())),
-            history_index: Arc::new(Mutex::new(None)),
+            histories: Mutex::new(HashMap::new()),
+            offline: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Set the size images should be downloaded at (capped to `ImageSize::MAX`)
+    pub fn set_target_size(&self, size: ImageSize) {
+        let size = size.capped();
+        let mut target = self.target_size.lock().unwrap();
+        if *target != size {
+            log::info!("Image target size: {}x{}", size.width, size.height);
+            *target = size;
+        }
+    }
+
+    fn fetch_context(&self) -> FetchContext {
+        FetchContext {
+            client: self.client.clone(),
+            target: *self.target_size.lock().unwrap(),
+            sources: self.source_settings.lock().unwrap().clone(),
+        }
+    }
+
+    pub fn sources(&self) -> &SourceRegistry {
+        &self.sources
+    }
+
+    pub fn source_settings(&self) -> SourceSettings {
+        SourceSettings::clone(&self.source_settings.lock().unwrap())
+    }
+
+    /// Change which sources are drawn from. Queued artwork from sources that
+    /// are now disabled is skipped.
+    pub fn set_source_settings(&self, settings: SourceSettings) {
+        *self.source_settings.lock().unwrap() = Arc::new(settings);
+    }
+
+    /// Like `set_source_settings`, but also drops queued artwork from sources
+    /// whose search terms or filters changed, so a new theme shows up right away
+    pub async fn update_source_settings(&self, settings: SourceSettings) {
+        let old = self.source_settings();
+        let stale: Vec<String> = self
+            .sources
+            .iter()
+            .map(|s| s.id_prefix())
+            .filter(|&prefix| {
+                let (old, new) = (old.get(prefix), settings.get(prefix));
+                old.terms != new.terms || old.filters != new.filters
+            })
+            .map(|prefix| format!("{prefix}-"))
+            .collect();
+        self.set_source_settings(settings);
+        if !stale.is_empty() {
+            self.cache
+                .lock()
+                .await
+                .retain(|art| !stale.iter().any(|prefix| art.id.starts_with(prefix.as_str())));
         }
     }
 
-    /// Start background prefetch loop
+    /// Whether we're currently serving artwork from the disk cache because the network is down
+    pub fn is_offline(&self) -> bool {
+        self.offline.load(Ordering::Relaxed)
+    }
+
+    pub fn favorites(&self) -> Result<&Favorites, ArtError> {
+        self.favorites
+            .as_deref()
+            .ok_or_else(|| ArtError::unsupported("Favorites are unavailable"))
+    }
+
+    pub fn favorites_only(&self) -> bool {
+        self.favorites_only.load(Ordering::Relaxed)
+    }
+
+    pub fn set_favorites_only(&self, enabled: bool) {
+        self.favorites_only.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Start background prefetch loop, seeded with any undisplayed artwork left on disk
     pub fn start_prefetch(&self) {
         let client = self.client.clone();
+        let target_size = self.target_size.clone();
+        let sources = self.sources.clone();
+        let source_settings = self.source_settings.clone();
+        let disk = self.disk.clone();
         let cache = self.cache.clone();
+        let offline = self.offline.clone();
 
         tauri::async_runtime::spawn(async move {
+            let restored = disk.load_unshown(CACHE_SIZE).await;
+            if !restored.is_empty() {
+                log::info!("Restored {} artworks from disk cache", restored.len());
+                cache.lock().await.extend(restored);
+            }
+
             loop {
+                let mut delay = 2;
                 let current_len = cache.lock().await.len();
                 if current_len < CACHE_SIZE {
-                    match fetch_random_artwork(&client).await {
-                        Ok(artwork) => {
-                            let mut c = cache.lock().await;
-                            if c.len() < CACHE_SIZE {
-                                log::info!(
-                                    "Cached artwork: {} (cache size: {})",
-                                    artwork.title,
-                                    c.len() + 1
-                                );
-                                c.push_back(artwork);
+                    let ctx = FetchContext {
+                        client: client.clone(),
+                        target: *target_size.lock().unwrap(),
+                        sources: source_settings.lock().unwrap().clone(),
+                    };
+                    match sources.fetch_random(&ctx).await {
+                        Ok(FetchedArtwork { artwork, image }) => {
+                            if offline.swap(false, Ordering::Relaxed) {
+                                log::info!("Network is back, leaving offline mode");
+                            }
+                            // The overlay loads images from the disk cache, so only queue what was persisted
+                            if let Err(e) = disk.store(&artwork, &image, false).await {
+                                log::warn!("Failed to persist artwork: {e}");
+                            } else {
+                                let mut c = cache.lock().await;
+                                if c.len() < CACHE_SIZE {
+                                    log::info!(
+                                        "Cached artwork: {} (cache size: {})",
+                                        artwork.title,
+                                        c.len() + 1
+                                    );
+                                    c.push_back(artwork);
+                                }
                             }
                         }
+                        Err(e) if e.is_connectivity() => {
+                            if !offline.swap(true, Ordering::Relaxed) {
+                                log::warn!("Network unavailable, switching to offline mode: {e}");
+                            }
+                            delay = OFFLINE_RETRY_SECS;
+                        }
                         Err(e) => {
                             log::error!("Prefetch failed: {e}");
                         }
                     }
                 }
-                tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+                tokio::time::sleep(tokio::time::Duration::from_secs(delay)).await;
             }
         });
     }
 
-    /// Get the next artwork (from cache or fetch live)
-    pub async fn next(&self) -> Result<Artwork, String> {
+    /// Get the next artwork for a history (from cache or fetch live).
+    ///
+    /// `screen` is the size of the monitor it will be shown on; queued artwork
+    /// that best matches its aspect ratio is preferred.
+    pub async fn next(&self, key: &str, screen: Option<ImageSize>) -> Result<Artwork, ArtError> {
         // If browsing history, move forward
-        {
-            let mut idx = self.history_index.lock().await;
-            if let Some(i) = *idx {
-                let history = self.history.lock().await;
-                if i + 1 < history.len() {
-                    *idx = Some(i + 1);
-                    return Ok(history[i + 1].clone());
-                } else {
-                    // At end of history, fall through to get new artwork
-                    *idx = None;
-                }
-            }
+        let forward = self.histories.lock().await.get_mut(key).and_then(History::forward);
+        if let Some(art) = forward {
+            return Ok(art);
         }
 
-        // Try cache first, then fetch live
-        let artwork = {
-            let mut cache = self.cache.lock().await;
-            if let Some(art) = cache.pop_front() {
-                art
-            } else {
-                drop(cache);
-                fetch_random_artwork(&self.client).await?
-            }
+        // In favorites-only mode, rotate through starred artwork
+        let favorite = if self.favorites_only() {
+            self.favorite_pick().await
+        } else {
+            None
+        };
+
+        let artwork = match favorite {
+            Some(art) => art,
+            None => self.take_new(screen).await?,
         };
+        self.record(key, &artwork).await;
+        Ok(artwork)
+    }
 
-        // Add to history
-        {
-            let mut history = self.history.lock().await;
-            history.push(artwork.clone());
-            // Keep history reasonable
-            if history.len() > 50 {
-                history.drain(0..25);
+    /// Like `next` for a history nobody browses, but fetched from the sources
+    /// so the prefetch queue is left for the overlays
+    pub async fn next_live(&self, key: &str) -> Result<Artwork, ArtError> {
+        let favorite = if self.favorites_only() {
+            self.favorite_pick().await
+        } else {
+            None
+        };
+        let artwork = match favorite {
+            Some(art) => art,
+            None => self.fetch_live().await?,
+        };
+        self.record(key, &artwork).await;
+        Ok(artwork)
+    }
+
+    async fn record(&self, key: &str, artwork: &Artwork) {
+        self.histories
+            .lock()
+            .await
+            .entry(key.into())
+            .or_default()
+            .push(artwork.clone());
+    }
+
+    /// Take new artwork from the prefetch queue, the offline pool, or the network
+    async fn take_new(&self, screen: Option<ImageSize>) -> Result<Artwork, ArtError> {
+        let popped = {
+            let settings = self.source_settings.lock().unwrap().clone();
+            let mut queue = self.cache.lock().await;
+            queue.retain(|art| settings.allows(&art.id));
+            let best = screen.and_then(|screen| {
+                // min_by keeps the first of equal scores, so ties go to the oldest
+                (0..queue.len()).min_by(|&a, &b| {
+                    aspect_mismatch(&queue[a], screen).total_cmp(&aspect_mismatch(&queue[b], screen))
+                })
+            });
+            queue.remove(best.unwrap_or(0))
+        };
+        if let Some(art) = popped {
+            self.disk.mark_shown(&art.id).await;
+            return Ok(art);
+        }
+        match self.offline_pick().await {
+            Some(art) => Ok(art),
+            None => self.fetch_live().await,
+        }
+    }
+
+    /// Fetch from the network, falling back to the disk cache if connectivity is lost
+    async fn fetch_live(&self) -> Result<Artwork, ArtError> {
+        match self.sources.fetch_random(&self.fetch_context()).await {
+            Ok(FetchedArtwork { artwork, image }) => {
+                self.offline.store(false, Ordering::Relaxed);
+                self.disk.store(&artwork, &image, true).await?;
+                Ok(artwork)
             }
+            Err(e) if e.is_connectivity() => {
+                log::warn!("Network unavailable, switching to offline mode: {e}");
+                self.offline.store(true, Ordering::Relaxed);
+                self.offline_pick().await.ok_or(e)
+            }
+            Err(e) => Err(e),
         }
+    }
 
-        Ok(artwork)
+    /// While offline, pick a random artwork from disk that wasn't shown recently
+    async fn offline_pick(&self) -> Option<Artwork> {
+        if !self.is_offline() {
+            return None;
+        }
+        let artwork = self.disk.random_excluding(&self.recent_ids().await).await;
+        if let Some(art) = &artwork {
+            log::info!("Offline: showing cached artwork {}", art.title);
+        }
+        artwork
     }
 
-    /// Go back in history
-    pub async fn prev(&self) -> Result<Artwork, String> {
-        let mut idx = self.history_index.lock().await;
-        let history = self.history.lock().await;
+    /// Pick a random favorite that wasn't shown recently
+    async fn favorite_pick(&self) -> Option<Artwork> {
+        let favorites = self.favorites.as_ref()?;
+        let artwork = favorites.random_excluding(&self.recent_ids().await);
+        if artwork.is_none() {
+            log::warn!("Favorites-only mode is on but there are no favorites");
+        }
+        artwork
+    }
+
+    /// Ids of the most recently shown artworks across all histories, used to avoid repeats
+    async fn recent_ids(&self) -> HashSet<String> {
+        let histories = self.histories.lock().await;
+        histories
+            .values()
+            .flat_map(|h| h.items.iter().rev().take(RECENT_WINDOW))
+            .map(|a| a.id.clone())
+            .collect()
+    }
 
-        if history.is_empty() {
-            return Err("No history".into());
+    /// Find an artwork we've already downloaded, in any history, the prefetch queue or favorites
+    pub async fn find(&self, id: &str) -> Option<Artwork> {
+        let found = self
+            .histories
+            .lock()
+            .await
+            .values()
+            .find_map(|h| h.items.iter().rev().find(|a| a.id == id).cloned());
+        if found.is_some() {
+            return found;
         }
+        let queued = self.cache.lock().await.iter().find(|a| a.id == id).cloned();
+        if queued.is_some() {
+            return queued;
+        }
+        self.favorites
+            .as_ref()
+            .and_then(|f| f.list().into_iter().find(|a| a.id == id))
+    }
 
-        let new_idx = match *idx {
-            Some(0) => return Err("At beginning of history".into()),
-            Some(i) => i - 1,
+    /// An artwork we've already downloaded, together with its image
+    pub async fn find_with_image(&self, id: &str) -> Option<(Artwork, ImageData)> {
+        let artwork = self.find(id).await?;
+        let image = self.image(id).await?;
+        Some((artwork, image))
+    }
+
+    /// Museum web page for an artwork: the URL its source reported if we still
+    /// have it, otherwise one built from the id
+    pub async fn object_url(&self, id: &str) -> Result<String, ArtError> {
+        match self.find(id).await.and_then(|a| a.object_url) {
+            Some(url) => Ok(url),
+            None => self.sources.object_url(id),
+        }
+    }
+
+    /// Image bytes for an artwork, from favorites or the disk cache
+    pub async fn image(&self, id: &str) -> Option<ImageData> {
+        if let Some(favorites) = &self.favorites {
+            if let Some(image) = favorites.image(id).await {
+                return Some(image);
+            }
+        }
+        self.disk.image(id).await
+    }
+
+    /// Star an artwork, fetching it from its source if we no longer have it
+    pub async fn favorite(&self, id: &str) -> Result<Artwork, ArtError> {
+        let favorites = self.favorites()?;
+        let (artwork, image) = match self.find_with_image(id).await {
+            Some(found) => found,
             None => {
-                // Start browsing from the last item
-                if history.len() >= 2 {
-                    history.len() - 2
-                } else {
-                    return Err("No previous artwork".into());
-                }
+                let fetched = self.sources.fetch_by_id(&self.fetch_context(), id).await?;
+                (fetched.artwork, fetched.image)
             }
         };
+        favorites.add(&artwork, &image).await?;
+        Ok(artwork)
+    }
 
-        *idx = Some(new_idx);
-        Ok(history[new_idx].clone())
+    /// Go back in a history
+    pub async fn prev(&self, key: &str) -> Result<Artwork, ArtError> {
+        match self.histories.lock().await.get_mut(key) {
+            Some(history) => history.back(),
+            None => Err(ArtError::history("No history")),
+        }
+    }
+
+    /// Get the current artwork of a history without advancing
+    pub async fn current(&self, key: &str) -> Option<Artwork> {
+        self.histories.lock().await.get(key).and_then(History::current)
     }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::art_api::{ArtSource, SourceCapabilities, SourcePrefs};
+    use async_trait::async_trait;
+
+    /// A source whose server can never be reached
+    struct Unreachable(&'static str);
+
+    #[async_trait]
+    impl ArtSource for Unreachable {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn id_prefix(&self) -> &str {
+            self.0
+        }
+
+        async fn fetch_random(&self, _ctx: &FetchContext) -> Result<FetchedArtwork, ArtError> {
+            Err(ArtError::network(self.0, "connection refused"))
+        }
+    }
+
+    /// A folder source with nothing in its folder
+    struct EmptyFolder;
+
+    #[async_trait]
+    impl ArtSource for EmptyFolder {
+        fn name(&self) -> &str {
+            "Empty"
+        }
+
+        fn id_prefix(&self) -> &str {
+            "empty"
+        }
 
-    /// Get current artwork without advancing
-    pub async fn current(&self) -> Option<Artwork> {
-        let idx = self.history_index.lock().await;
-        let history = self.history.lock().await;
+        fn capabilities(&self) -> SourceCapabilities {
+            SourceCapabilities {
+                folder: true,
+                ..SourceCapabilities::default()
+            }
+        }
 
-        match *idx {
-            Some(i) => history.get(i).cloned(),
-            None => history.last().cloned(),
+        async fn fetch_random(&self, _ctx: &FetchContext) -> Result<FetchedArtwork, ArtError> {
+            Err(ArtError::no_results("Empty", "no images"))
         }
     }
+
+    pub(crate) fn artwork(id: &str) -> Artwork {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "title": "Cached",
+            "artist": "Someone",
+            "date": "",
+            "medium": "",
+            "source": "Test",
+            "image_url": "",
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_disk_when_every_source_is_unreachable() {
+        let dir = tempfile::tempdir().unwrap();
+        let disk = DiskCache::open(dir.path().into(), u64::MAX).unwrap();
+        let image = ImageData {
+            bytes: vec![0; 16],
+            mime: "image/jpeg".into(),
+        };
+        disk.store(&artwork("a-1"), &image, true).await.unwrap();
+
+        let mut sources = SourceRegistry::new();
+        sources.register(Unreachable("a"));
+        sources.register(Unreachable("b"));
+        let cache = ArtCache::new(sources, disk, None);
+
+        let shown = cache.next(SHARED_HISTORY, None).await.unwrap();
+        assert_eq!(shown.id, "a-1");
+        assert!(cache.is_offline());
+    }
+
+    #[tokio::test]
+    async fn reports_the_network_error_when_nothing_is_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let disk = DiskCache::open(dir.path().into(), u64::MAX).unwrap();
+        let mut sources = SourceRegistry::new();
+        sources.register(Unreachable("a"));
+        let cache = ArtCache::new(sources, disk, None);
+
+        let err = cache.next(SHARED_HISTORY, None).await.unwrap_err();
+        assert!(err.is_connectivity());
+        assert!(cache.is_offline());
+    }
+
+    #[tokio::test]
+    async fn a_failing_folder_source_does_not_hide_a_lost_connection() {
+        let dir = tempfile::tempdir().unwrap();
+        let disk = DiskCache::open(dir.path().join("cache"), u64::MAX).unwrap();
+        let mut sources = SourceRegistry::new();
+        sources.register(Unreachable("a"));
+        sources.register(EmptyFolder);
+        let cache = ArtCache::new(sources, disk, None);
+        let folder = SourcePrefs {
+            folder: Some(dir.path().to_string_lossy().into_owned()),
+            ..SourcePrefs::default()
+        };
+        cache.set_source_settings(SourceSettings([("empty".to_string(), folder)].into()));
+
+        let err = cache.next(SHARED_HISTORY, None).await.unwrap_err();
+        assert!(err.is_connectivity());
+        assert!(cache.is_offline());
+    }
 }
@@ -1,37 +1,196 @@
-use crate::art_cache::ArtCache;
-use crate::hotkey;
-use crate::windows;
+use crate::art_api::{SourceFilter, SourcePrefs, SourceSettings};
+use crate::art_cache::{ArtCache, SHARED_HISTORY};
+use crate::error::ArtError;
+use crate::hotkey::{self, HotkeyAction, Keymap};
+use crate::idle::ScreensaverSettings;
+use crate::save;
+use crate::slideshow::{Slideshow, SlideshowSettings};
+use crate::wallpaper::{self, Wallpaper, WallpaperSettings};
+use crate::windows::{self, DisplayMode};
 use crate::Artwork;
-use tauri::{AppHandle, Emitter, Manager, State};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State, WebviewWindow};
+use tauri_plugin_opener::OpenerExt;
 use tauri_plugin_store::StoreExt;
 
+/// Payload of the `favorite-changed` event
+#[derive(Clone, Serialize)]
+struct FavoriteChanged<'a> {
+    id: &'a str,
+    favorite: bool,
+}
+
+/// History a window navigates: its own in per-monitor mode, otherwise the shared one
+fn history_key(app: &AppHandle, window: &WebviewWindow) -> String {
+    match windows::display_mode(app) {
+        DisplayMode::PerMonitor => window.label().to_string(),
+        DisplayMode::Mirror | DisplayMode::Span => SHARED_HISTORY.to_string(),
+    }
+}
+
+/// Histories the open overlays show: one per overlay in per-monitor mode, otherwise the shared one
+fn overlay_history_keys(app: &AppHandle) -> Vec<String> {
+    match windows::display_mode(app) {
+        DisplayMode::PerMonitor => windows::overlay_labels(app),
+        DisplayMode::Mirror | DisplayMode::Span => vec![SHARED_HISTORY.to_string()],
+    }
+}
+
+/// History of the overlay the user is most likely looking at: the focused
+/// overlay in per-monitor mode, otherwise the shared history
+fn active_history_key(app: &AppHandle) -> String {
+    if windows::display_mode(app) != DisplayMode::PerMonitor {
+        return SHARED_HISTORY.to_string();
+    }
+    let labels = windows::overlay_labels(app);
+    labels
+        .iter()
+        .find(|label| {
+            app.get_webview_window(label)
+                .and_then(|w| w.is_focused().ok())
+                .unwrap_or(false)
+        })
+        .or(labels.first())
+        .cloned()
+        .unwrap_or_else(|| SHARED_HISTORY.to_string())
+}
+
+/// Send artwork to the overlays showing history `key`
+fn emit_artwork(app: &AppHandle, key: &str, artwork: &Artwork) {
+    let _ = if key == SHARED_HISTORY {
+        // Emit to all overlay windows
+        app.emit("artwork-changed", artwork)
+    } else {
+        app.emit_to(key, "artwork-changed", artwork)
+    };
+}
+
 #[tauri::command]
-pub async fn get_current_artwork(cache: State<'_, ArtCache>) -> Result<Option<Artwork>, String> {
-    Ok(cache.current().await)
+pub async fn get_current_artwork(
+    app: AppHandle,
+    window: WebviewWindow,
+    cache: State<'_, ArtCache>,
+) -> Result<Option<Artwork>, ArtError> {
+    Ok(cache.current(&history_key(&app, &window)).await)
 }
 
 #[tauri::command]
-pub async fn next_artwork(app: AppHandle, cache: State<'_, ArtCache>) -> Result<Artwork, String> {
-    let artwork = cache.next().await?;
-    // Emit to all overlay windows
-    let _ = app.emit("artwork-changed", &artwork);
+pub async fn next_artwork(
+    app: AppHandle,
+    window: WebviewWindow,
+    cache: State<'_, ArtCache>,
+    slideshow: State<'_, Slideshow>,
+) -> Result<Artwork, ArtError> {
+    slideshow.touch();
+    let key = history_key(&app, &window);
+    let artwork = cache.next(&key, windows::screen_size(&app, &key)).await?;
+    emit_artwork(&app, &key, &artwork);
     Ok(artwork)
 }
 
 #[tauri::command]
-pub async fn prev_artwork(app: AppHandle, cache: State<'_, ArtCache>) -> Result<Artwork, String> {
-    let artwork = cache.prev().await?;
-    let _ = app.emit("artwork-changed", &artwork);
+pub async fn prev_artwork(
+    app: AppHandle,
+    window: WebviewWindow,
+    cache: State<'_, ArtCache>,
+    slideshow: State<'_, Slideshow>,
+) -> Result<Artwork, ArtError> {
+    slideshow.touch();
+    let key = history_key(&app, &window);
+    let artwork = cache.prev(&key).await?;
+    emit_artwork(&app, &key, &artwork);
     Ok(artwork)
 }
 
+/// Which part of the artwork the calling overlay shows, or `None` unless spanning
+#[tauri::command]
+pub fn get_span_slice(app: AppHandle, window: WebviewWindow) -> Option<windows::SpanSlice> {
+    if windows::display_mode(&app) != DisplayMode::Span {
+        return None;
+    }
+    windows::span_slice(&app, window.label())
+}
+
+/// Open an artwork's page on its museum's website in the default browser
+#[tauri::command]
+pub async fn open_artwork_page(app: AppHandle, id: String) -> Result<(), ArtError> {
+    open_page(&app, &id).await
+}
+
+/// Save an artwork's image (plus a metadata sidecar) to `dir`, or the configured
+/// save folder. Returns where the image was written.
+#[tauri::command]
+pub async fn save_artwork(
+    app: AppHandle,
+    cache: State<'_, ArtCache>,
+    id: String,
+    dir: Option<String>,
+) -> Result<String, ArtError> {
+    let (artwork, image) = cache
+        .find_with_image(&id)
+        .await
+        .ok_or_else(|| ArtError::cache(format!("No downloaded image for {id}")))?;
+    let dir = match dir.filter(|d| !d.is_empty()) {
+        Some(dir) => dir.into(),
+        None => save::save_dir(&app)?,
+    };
+    let path = save::save_artwork(&artwork, &image, &dir).await?;
+    Ok(path.display().to_string())
+}
+
+#[tauri::command]
+pub fn get_save_dir(app: AppHandle) -> Result<String, ArtError> {
+    Ok(save::save_dir(&app)?.display().to_string())
+}
+
+/// Set the folder `save_artwork` uses by default; empty resets to Pictures/Art
+#[tauri::command]
+pub fn set_save_dir(app: AppHandle, dir: String) -> Result<(), ArtError> {
+    let store = app.store("settings.json").map_err(ArtError::store)?;
+    store.set("save_dir", serde_json::Value::String(dir));
+    Ok(())
+}
+
+/// Make an artwork we've shown the desktop background
+#[tauri::command]
+pub async fn set_as_wallpaper(
+    cache: State<'_, ArtCache>,
+    wallpaper: State<'_, Wallpaper>,
+    id: String,
+) -> Result<(), ArtError> {
+    wallpaper.apply_cached(&cache, &id).await?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_wallpaper(app: AppHandle) -> WallpaperSettings {
+    WallpaperSettings::load(&app)
+}
+
+/// Save wallpaper settings; turning rotation on changes the wallpaper right away
+#[tauri::command]
+pub async fn set_wallpaper(app: AppHandle, settings: WallpaperSettings) -> Result<(), ArtError> {
+    let was_rotating = WallpaperSettings::load(&app).rotate_daily;
+    settings.save(&app)?;
+    if settings.rotate_daily && !was_rotating {
+        wallpaper::rotate(&app).await?;
+    }
+    Ok(())
+}
+
+/// Whether artwork is currently being served from the disk cache because the network is down
+#[tauri::command]
+pub fn is_offline(cache: State<'_, ArtCache>) -> bool {
+    cache.is_offline()
+}
+
 #[tauri::command]
 pub fn overlay_ready(app: AppHandle) {
     windows::show_overlay_windows(&app);
 }
 
 #[tauri::command]
-pub async fn dismiss_overlays(app: AppHandle) -> Result<(), String> {
+pub async fn dismiss_overlays(app: AppHandle) -> Result<(), ArtError> {
     // Defer the close so the IPC response is sent before the webview is destroyed.
     // Without this, calling dismiss from inside the overlay's own webview panics
     // because destroy() kills the IPC channel before Ok(()) can be returned.
@@ -42,41 +201,192 @@ pub async fn dismiss_overlays(app: AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// Star an artwork we've shown (or can fetch by id)
+#[tauri::command]
+pub async fn favorite_artwork(
+    app: AppHandle,
+    cache: State<'_, ArtCache>,
+    slideshow: State<'_, Slideshow>,
+    id: String,
+) -> Result<Artwork, ArtError> {
+    slideshow.touch();
+    let artwork = cache.favorite(&id).await?;
+    let _ = app.emit("favorite-changed", FavoriteChanged { id: &id, favorite: true });
+    Ok(artwork)
+}
+
+#[tauri::command]
+pub async fn unfavorite_artwork(
+    app: AppHandle,
+    cache: State<'_, ArtCache>,
+    id: String,
+) -> Result<(), ArtError> {
+    cache.favorites()?.remove(&id).await?;
+    let _ = app.emit("favorite-changed", FavoriteChanged { id: &id, favorite: false });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_favorites(cache: State<'_, ArtCache>) -> Result<Vec<Artwork>, ArtError> {
+    Ok(cache.favorites()?.list())
+}
+
+#[tauri::command]
+pub fn is_favorite(cache: State<'_, ArtCache>, id: String) -> Result<bool, ArtError> {
+    Ok(cache.favorites()?.contains(&id))
+}
+
+#[tauri::command]
+pub fn get_favorites_only(cache: State<'_, ArtCache>) -> bool {
+    cache.favorites_only()
+}
+
+/// Switch between rotating through all sources and rotating through favorites only
+#[tauri::command]
+pub fn set_favorites_only(
+    app: AppHandle,
+    cache: State<'_, ArtCache>,
+    enabled: bool,
+) -> Result<(), ArtError> {
+    if enabled && cache.favorites()?.is_empty() {
+        return Err(ArtError::unsupported("Star some artwork before enabling favorites only"));
+    }
+    cache.set_favorites_only(enabled);
+
+    let store = app.store("settings.json").map_err(ArtError::store)?;
+    store.set("favorites_only", serde_json::Value::Bool(enabled));
+    Ok(())
+}
+
+/// A source as listed in settings
+#[derive(Serialize)]
+pub struct SourceInfo {
+    id: String,
+    name: String,
+    #[serde(flatten)]
+    prefs: SourcePrefs,
+    /// Whether `terms` can be set
+    searchable: bool,
+    /// Whether the source is skipped until `api_key` is set
+    needs_api_key: bool,
+    /// Whether the source is skipped until `folder` is set
+    needs_folder: bool,
+    default_terms: Vec<&'static str>,
+    available_filters: Vec<SourceFilter>,
+}
+
 #[tauri::command]
-pub async fn get_hotkey(app: AppHandle) -> Result<String, String> {
-    let store = app
-        .store("settings.json")
-        .map_err(|e| format!("Store error: {e}"))?;
-    let hotkey = store
-        .get("hotkey")
-        .and_then(|v| v.as_str().map(String::from))
-        .unwrap_or_else(|| hotkey::DEFAULT_HOTKEY.to_string());
-    Ok(hotkey)
+pub fn get_sources(cache: State<'_, ArtCache>) -> Vec<SourceInfo> {
+    let settings = cache.source_settings();
+    cache
+        .sources()
+        .iter()
+        .map(|s| SourceInfo {
+            id: s.id_prefix().into(),
+            name: s.name().into(),
+            prefs: settings.get(s.id_prefix()),
+            searchable: s.capabilities().search,
+            needs_api_key: s.capabilities().api_key,
+            needs_folder: s.capabilities().folder,
+            default_terms: s.default_terms().to_vec(),
+            available_filters: s.filters().to_vec(),
+        })
+        .collect()
 }
 
+/// Choose which sources artwork is drawn from, how often, and what to search them for
 #[tauri::command]
-pub async fn set_hotkey(app: AppHandle, hotkey: String) -> Result<(), String> {
-    // Try to register the new hotkey first
-    hotkey::register_hotkey(&app, &hotkey)?;
+pub async fn set_sources(
+    app: AppHandle,
+    cache: State<'_, ArtCache>,
+    sources: SourceSettings,
+) -> Result<(), ArtError> {
+    cache.sources().validate_settings(&sources)?;
+    let store = app.store("settings.json").map_err(ArtError::store)?;
+    store.set("sources", serde_json::to_value(&sources).map_err(ArtError::store)?);
+    cache.update_source_settings(sources).await;
+    Ok(())
+}
 
-    // Save to store
-    let store = app
-        .store("settings.json")
-        .map_err(|e| format!("Store error: {e}"))?;
-    store.set("hotkey", serde_json::Value::String(hotkey));
+#[tauri::command]
+pub fn get_display_mode(app: AppHandle) -> DisplayMode {
+    windows::display_mode(&app)
+}
 
+/// Choose between mirroring one artwork, showing different artwork per monitor,
+/// and spanning one artwork across all monitors.
+/// Takes effect the next time the overlays open.
+#[tauri::command]
+pub fn set_display_mode(app: AppHandle, mode: DisplayMode) -> Result<(), ArtError> {
+    let store = app.store("settings.json").map_err(ArtError::store)?;
+    store.set("display_mode", serde_json::to_value(mode).map_err(ArtError::store)?);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_slideshow(app: AppHandle) -> SlideshowSettings {
+    SlideshowSettings::load(&app)
+}
+
+/// Save slideshow settings, applying them right away if the overlays are open
+#[tauri::command]
+pub fn set_slideshow(
+    app: AppHandle,
+    slideshow: State<'_, Slideshow>,
+    settings: SlideshowSettings,
+) -> Result<(), ArtError> {
+    settings.save(&app)?;
+    if windows::overlays_open(&app) {
+        slideshow.start(&app);
+    }
     Ok(())
 }
 
+/// Pause or resume the running slideshow; returns whether it is now paused
+#[tauri::command]
+pub fn toggle_slideshow_pause(app: AppHandle) -> Result<bool, ArtError> {
+    toggle_slideshow(&app)
+}
+
+#[tauri::command]
+pub fn get_screensaver(app: AppHandle) -> ScreensaverSettings {
+    ScreensaverSettings::load(&app)
+}
+
+/// Save screensaver settings; the idle watcher picks them up on its next poll
+#[tauri::command]
+pub fn set_screensaver(app: AppHandle, settings: ScreensaverSettings) -> Result<(), ArtError> {
+    settings.save(&app)
+}
+
+#[tauri::command]
+pub fn get_hotkeys(app: AppHandle) -> Keymap {
+    hotkey::load_keymap(&app)
+}
+
+/// Check a shortcut before binding it to `action`. Conflicts are checked against
+/// `hotkeys` (the keymap being edited), or the saved keymap if omitted.
+#[tauri::command]
+pub fn validate_hotkey(
+    app: AppHandle,
+    action: HotkeyAction,
+    shortcut: String,
+    hotkeys: Option<Keymap>,
+) -> Result<(), ArtError> {
+    let keymap = hotkeys.unwrap_or_else(|| hotkey::load_keymap(&app));
+    hotkey::validate(&app, action, &shortcut, &keymap)
+}
+
+/// Register the new hotkeys, keeping the old ones if any of them fails
+#[tauri::command]
+pub fn set_hotkeys(app: AppHandle, hotkeys: Keymap) -> Result<(), ArtError> {
+    hotkey::swap_keymap(&app, &hotkey::load_keymap(&app), &hotkeys)?;
+    hotkey::save_keymap(&app, &hotkeys)
+}
+
 /// Toggle overlay display - called from hotkey and tray
 pub async fn toggle_overlays(app: AppHandle) {
-    // Check if overlays are currently shown
-    let has_overlays = app
-        .webview_windows()
-        .keys()
-        .any(|label| label.starts_with("overlay-"));
-
-    if has_overlays {
+    if windows::overlays_open(&app) {
         windows::close_overlay_windows(&app);
     } else {
         show_art(app).await;
@@ -85,15 +395,22 @@ pub async fn toggle_overlays(app: AppHandle) {
 
 /// Show artwork on all monitors
 pub async fn show_art(app: AppHandle) {
-    // Get artwork first
+    // Get artwork first, sized for the current monitors
     let cache = app.state::<ArtCache>();
-    let artwork = match cache.next().await {
-        Ok(art) => art,
-        Err(e) => {
-            log::error!("Failed to get artwork: {e}");
-            return;
+    if let Some(size) = windows::monitor_target_size(&app) {
+        cache.set_target_size(size);
+    }
+    let keys = overlay_history_keys(&app);
+    let mut artworks = Vec::with_capacity(keys.len());
+    for key in keys {
+        match cache.next(&key, windows::screen_size(&app, &key)).await {
+            Ok(art) => artworks.push((key, art)),
+            Err(e) => {
+                log::error!("Failed to get artwork: {e}");
+                return;
+            }
         }
-    };
+    }
 
     // Create overlay windows (created hidden — frontend calls overlay_ready once image loads)
     if let Err(e) = windows::create_overlay_windows(&app) {
@@ -102,5 +419,75 @@ pub async fn show_art(app: AppHandle) {
     }
 
     // Emit artwork immediately; the frontend also calls get_current_artwork on mount as fallback
-    let _ = app.emit("artwork-changed", &artwork);
+    for (key, artwork) in &artworks {
+        emit_artwork(&app, key, artwork);
+    }
+
+    app.state::<Slideshow>().start(&app);
+}
+
+/// Move every open overlay on to new artwork (used by the slideshow)
+pub async fn advance_overlays(app: &AppHandle) -> Result<(), ArtError> {
+    let cache = app.state::<ArtCache>();
+    for key in overlay_history_keys(app) {
+        let artwork = cache.next(&key, windows::screen_size(app, &key)).await?;
+        emit_artwork(app, &key, &artwork);
+    }
+    Ok(())
+}
+
+/// Move the open overlays forward or back (used by the next/prev hotkeys)
+pub async fn step_overlays(app: &AppHandle, forward: bool) -> Result<(), ArtError> {
+    if !windows::overlays_open(app) {
+        return Ok(());
+    }
+    app.state::<Slideshow>().touch();
+    let key = active_history_key(app);
+    let cache = app.state::<ArtCache>();
+    let artwork = if forward {
+        cache.next(&key, windows::screen_size(app, &key)).await?
+    } else {
+        cache.prev(&key).await?
+    };
+    emit_artwork(app, &key, &artwork);
+    Ok(())
+}
+
+/// Star or unstar the artwork on screen (used by the favorite hotkey)
+pub async fn toggle_current_favorite(app: &AppHandle) -> Result<(), ArtError> {
+    let cache = app.state::<ArtCache>();
+    let Some(artwork) = cache.current(&active_history_key(app)).await else {
+        return Ok(());
+    };
+    let favorite = !cache.favorites()?.contains(&artwork.id);
+    if favorite {
+        cache.favorite(&artwork.id).await?;
+    } else {
+        cache.favorites()?.remove(&artwork.id).await?;
+    }
+    let _ = app.emit("favorite-changed", FavoriteChanged { id: &artwork.id, favorite });
+    Ok(())
+}
+
+/// Open the museum page of the artwork on screen (used by the open-in-browser hotkey)
+pub async fn open_current_page(app: &AppHandle) -> Result<(), ArtError> {
+    let Some(artwork) = app.state::<ArtCache>().current(&active_history_key(app)).await else {
+        return Ok(());
+    };
+    open_page(app, &artwork.id).await
+}
+
+async fn open_page(app: &AppHandle, id: &str) -> Result<(), ArtError> {
+    let url = app.state::<ArtCache>().object_url(id).await?;
+    log::info!("Opening {url}");
+    app.opener()
+        .open_url(url, None::<&str>)
+        .map_err(|e| ArtError::unsupported(format!("Failed to open browser: {e}")))
+}
+
+/// Pause or resume the slideshow and tell the overlays; returns whether it is now paused
+pub fn toggle_slideshow(app: &AppHandle) -> Result<bool, ArtError> {
+    let paused = app.state::<Slideshow>().toggle_pause()?;
+    let _ = app.emit("slideshow-paused", paused);
+    Ok(paused)
 }
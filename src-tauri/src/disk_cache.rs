@@ -0,0 +1,257 @@
+use crate::art_api::ImageData;
+use crate::error::ArtError;
+use crate::Artwork;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Default upper bound on the total size of the on-disk cache
+pub const DEFAULT_MAX_BYTES: u64 = 200 * 1024 * 1024;
+
+/// Metadata written next to each cached image as `<stem>.json`
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    artwork: Artwork,
+    image_file: String,
+    mime: String,
+    /// Whether the artwork has been displayed. Unshown entries are restored
+    /// into the prefetch queue on startup; shown ones only serve as a fallback.
+    shown: bool,
+}
+
+/// Downloaded artwork persisted under the app data directory so restarts start warm.
+///
+/// Each artwork is stored as an image file plus a JSON metadata file. When the
+/// directory grows past `max_bytes`, the least recently written entries are evicted.
+pub struct DiskCache {
+    dir: PathBuf,
+    max_bytes: u64,
+}
+
+impl DiskCache {
+    pub fn open(dir: PathBuf, max_bytes: u64) -> Result<Self, ArtError> {
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| ArtError::cache(format!("Failed to create {}: {e}", dir.display())))?;
+        Ok(Self { dir, max_bytes })
+    }
+
+    /// Write an artwork and its image to disk, then enforce the size bound
+    pub async fn store(&self, artwork: &Artwork, image: &ImageData, shown: bool) -> Result<(), ArtError> {
+        let stem = file_stem(&artwork.id);
+        let image_file = format!("{stem}.{}", extension_for_mime(&image.mime));
+        tokio::fs::write(self.dir.join(&image_file), &image.bytes)
+            .await
+            .map_err(|e| ArtError::cache(format!("Failed to write {image_file}: {e}")))?;
+
+        let entry = CacheEntry {
+            artwork: artwork.clone(),
+            image_file,
+            mime: image.mime.clone(),
+            shown,
+        };
+        self.write_entry(&stem, &entry).await?;
+        self.evict().await;
+        Ok(())
+    }
+
+    /// Read the image bytes for a cached artwork
+    pub async fn image(&self, id: &str) -> Option<ImageData> {
+        let entry = self.read_entry(&self.entry_path(id)).await?;
+        let bytes = tokio::fs::read(self.dir.join(&entry.image_file)).await.ok()?;
+        Some(ImageData {
+            bytes,
+            mime: entry.mime,
+        })
+    }
+
+    /// Flag a cached artwork as displayed so it isn't restored into the queue again
+    pub async fn mark_shown(&self, id: &str) {
+        let stem = file_stem(id);
+        if let Some(mut entry) = self.read_entry(&self.entry_path(id)).await {
+            entry.shown = true;
+            if let Err(e) = self.write_entry(&stem, &entry).await {
+                log::warn!("Failed to update cache entry {id}: {e}");
+            }
+        }
+    }
+
+    /// Load up to `limit` artworks that were cached but never displayed, oldest first
+    pub async fn load_unshown(&self, limit: usize) -> Vec<Artwork> {
+        let mut entries = self.entries().await;
+        entries.retain(|(_, _, entry)| !entry.shown);
+        entries.sort_by_key(|(_, modified, _)| *modified);
+
+        let mut artworks = Vec::new();
+        for (_, _, entry) in entries {
+            if artworks.len() >= limit {
+                break;
+            }
+            if self.has_image(&entry).await {
+                artworks.push(entry.artwork);
+            }
+        }
+        artworks
+    }
+
+    /// Pick a random cached artwork, avoiding `exclude` unless nothing else is left
+    pub async fn random_excluding(&self, exclude: &HashSet<String>) -> Option<Artwork> {
+        let mut entries: Vec<CacheEntry> =
+            self.entries().await.into_iter().map(|(_, _, e)| e).collect();
+        if entries.iter().any(|e| !exclude.contains(&e.artwork.id)) {
+            entries.retain(|e| !exclude.contains(&e.artwork.id));
+        }
+
+        if entries.is_empty() {
+            return None;
+        }
+        let entry = entries.swap_remove(rand::rng().random_range(0..entries.len()));
+        self.has_image(&entry).await.then_some(entry.artwork)
+    }
+
+    /// Read all metadata files as (path, modified time, entry)
+    async fn entries(&self) -> Vec<(PathBuf, std::time::SystemTime, CacheEntry)> {
+        let mut result = Vec::new();
+        let Ok(mut dir) = tokio::fs::read_dir(&self.dir).await else {
+            return result;
+        };
+        while let Ok(Some(item)) = dir.next_entry().await {
+            let path = item.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let modified = match item.metadata().await.and_then(|m| m.modified()) {
+                Ok(t) => t,
+                Err(_) => continue,
+            };
+            if let Some(entry) = self.read_entry(&path).await {
+                result.push((path, modified, entry));
+            }
+        }
+        result
+    }
+
+    fn entry_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", file_stem(id)))
+    }
+
+    async fn has_image(&self, entry: &CacheEntry) -> bool {
+        tokio::fs::try_exists(self.dir.join(&entry.image_file))
+            .await
+            .unwrap_or(false)
+    }
+
+    async fn read_entry(&self, path: &Path) -> Option<CacheEntry> {
+        let json = tokio::fs::read(path).await.ok()?;
+        serde_json::from_slice(&json).ok()
+    }
+
+    async fn write_entry(&self, stem: &str, entry: &CacheEntry) -> Result<(), ArtError> {
+        let json = serde_json::to_vec(entry).map_err(ArtError::cache)?;
+        tokio::fs::write(self.dir.join(format!("{stem}.json")), json)
+            .await
+            .map_err(|e| ArtError::cache(format!("Failed to write {stem}.json: {e}")))
+    }
+
+    /// Delete entries until the cache fits in `max_bytes`: shown ones first, as
+    /// unshown ones may still be waiting in the prefetch queue, then oldest first
+    async fn evict(&self) {
+        let mut entries = Vec::new();
+        let mut total: u64 = 0;
+        for (path, modified, entry) in self.entries().await {
+            let image_path = self.dir.join(&entry.image_file);
+            let size = file_size(&path).await + file_size(&image_path).await;
+            total += size;
+            entries.push((entry.shown, modified, path, image_path, size));
+        }
+        if total <= self.max_bytes {
+            return;
+        }
+
+        entries.sort_by_key(|(shown, modified, ..)| (!shown, *modified));
+        for (_, _, path, image_path, size) in entries {
+            if total <= self.max_bytes {
+                break;
+            }
+            let _ = tokio::fs::remove_file(&image_path).await;
+            let _ = tokio::fs::remove_file(&path).await;
+            total = total.saturating_sub(size);
+            log::info!("Evicted {} from disk cache", path.display());
+        }
+    }
+}
+
+async fn file_size(path: &Path) -> u64 {
+    tokio::fs::metadata(path).await.map(|m| m.len()).unwrap_or(0)
+}
+
+/// Turn an artwork id into a safe file name stem.
+///
+/// Ids that are already safe are used as is. Others (local file paths, say) get
+/// a hash of the full id appended, so `a/b.jpg` and `a_b.jpg` don't share a file.
+pub(crate) fn file_stem(id: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+    if id.chars().all(safe) {
+        return id.into();
+    }
+    let readable: String = id
+        .chars()
+        .map(|c| if safe(c) { c } else { '_' })
+        .take(80)
+        .collect();
+    format!("{readable}-{:016x}", fnv1a(id.as_bytes()))
+}
+
+/// 64-bit FNV-1a. File names have to stay the same across Rust releases, which
+/// `DefaultHasher` doesn't promise.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+pub(crate) fn extension_for_mime(mime: &str) -> &'static str {
+    match mime {
+        "image/png" => "png",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/tiff" => "tif",
+        _ => "jpg",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::art_cache::tests::artwork;
+
+    #[test]
+    fn safe_ids_are_kept_as_they_are() {
+        assert_eq!(file_stem("met-436535"), "met-436535");
+        assert_eq!(file_stem("nga-00209fb1-64a2"), "nga-00209fb1-64a2");
+    }
+
+    #[test]
+    fn ids_that_differ_only_in_unsafe_characters_get_different_stems() {
+        assert_ne!(file_stem("local-a/b.jpg"), file_stem("local-a_b.jpg"));
+        assert_ne!(file_stem("local-café.jpg"), file_stem("local-caf_.jpg"));
+        assert_ne!(file_stem("local-a_b_jpg"), file_stem("local-a_b.jpg"));
+    }
+
+    #[tokio::test]
+    async fn evicts_shown_artwork_before_queued_artwork() {
+        let dir = tempfile::tempdir().unwrap();
+        let disk = DiskCache::open(dir.path().into(), 3200).unwrap();
+        let image = ImageData {
+            bytes: vec![0; 1000],
+            mime: "image/jpeg".into(),
+        };
+        disk.store(&artwork("t-1"), &image, false).await.unwrap();
+        disk.store(&artwork("t-2"), &image, true).await.unwrap();
+        disk.store(&artwork("t-3"), &image, false).await.unwrap();
+
+        assert!(disk.image("t-1").await.is_some());
+        assert!(disk.image("t-2").await.is_none());
+        assert!(disk.image("t-3").await.is_some());
+    }
+}
@@ -0,0 +1,167 @@
+use serde::Serialize;
+
+/// Errors from the fetch pipeline, the artwork cache and the Tauri commands.
+///
+/// Serialized as a tagged object (`{ "kind": "network", "source": "Met", ... }`)
+/// so the frontend can tell failures apart without parsing messages.
+#[derive(Debug, Clone, Serialize, thiserror::Error)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ArtError {
+    /// The request never got a response (DNS, connection refused, timeout...)
+    #[error("{source_name}: network error: {message}")]
+    Network {
+        #[serde(rename = "source")]
+        source_name: String,
+        message: String,
+    },
+
+    /// The server answered with a non-success status
+    #[error("{source_name}: HTTP {status} from {url}")]
+    HttpStatus {
+        #[serde(rename = "source")]
+        source_name: String,
+        status: u16,
+        url: String,
+    },
+
+    /// The response body wasn't what we expected
+    #[error("{source_name}: failed to parse response: {message}")]
+    Parse {
+        #[serde(rename = "source")]
+        source_name: String,
+        message: String,
+    },
+
+    /// The source answered but had nothing usable
+    #[error("{source_name}: {message}")]
+    NoResults {
+        #[serde(rename = "source")]
+        source_name: String,
+        message: String,
+    },
+
+    /// An image download failed validation (status, content type, size)
+    #[error("Image rejected ({reason}): {url}")]
+    ImageRejected { url: String, reason: String },
+
+    /// Every registered source failed; `errors` holds one entry per source.
+    /// `offline` is set when every source that goes over the network failed to connect.
+    #[error("All sources failed. Last error: {}", last_message(.errors))]
+    AllSourcesFailed { errors: Vec<ArtError>, offline: bool },
+
+    /// Navigating past either end of the history
+    #[error("{message}")]
+    HistoryBoundary { message: String },
+
+    /// The operation isn't available (unknown source, lookup not supported...)
+    #[error("{message}")]
+    Unsupported { message: String },
+
+    /// A setting was given a value we can't use
+    #[error("{message}")]
+    Invalid { message: String },
+
+    #[error("Store error: {message}")]
+    Store { message: String },
+
+    /// Reading or writing the on-disk artwork cache failed
+    #[error("Cache error: {message}")]
+    Cache { message: String },
+
+    #[error("{message}")]
+    Window { message: String },
+
+    #[error("Can't use shortcut '{shortcut}': {message}")]
+    Hotkey {
+        shortcut: String,
+        problem: HotkeyProblem,
+        message: String,
+    },
+}
+
+/// Why a shortcut can't be used
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HotkeyProblem {
+    /// Not a valid accelerator, or one that would capture plain typing
+    Invalid,
+    /// Another action in the keymap uses it
+    Duplicate,
+    /// The system or another application already holds it
+    Taken,
+}
+
+fn last_message(errors: &[ArtError]) -> String {
+    errors
+        .last()
+        .map(ToString::to_string)
+        .unwrap_or_else(|| "no sources registered".into())
+}
+
+impl ArtError {
+    /// Whether this failure looks like lost connectivity rather than a bad response
+    pub fn is_connectivity(&self) -> bool {
+        match self {
+            Self::Network { .. } => true,
+            Self::AllSourcesFailed { offline, .. } => *offline,
+            _ => false,
+        }
+    }
+
+    pub fn network(source_name: &str, err: impl std::fmt::Display) -> Self {
+        Self::Network {
+            source_name: source_name.into(),
+            message: err.to_string(),
+        }
+    }
+
+    pub fn parse(source_name: &str, err: impl std::fmt::Display) -> Self {
+        Self::Parse {
+            source_name: source_name.into(),
+            message: err.to_string(),
+        }
+    }
+
+    pub fn no_results(source_name: &str, message: impl Into<String>) -> Self {
+        Self::NoResults {
+            source_name: source_name.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn history(message: impl Into<String>) -> Self {
+        Self::HistoryBoundary {
+            message: message.into(),
+        }
+    }
+
+    pub fn unsupported(message: impl Into<String>) -> Self {
+        Self::Unsupported {
+            message: message.into(),
+        }
+    }
+
+    pub fn invalid(message: impl Into<String>) -> Self {
+        Self::Invalid {
+            message: message.into(),
+        }
+    }
+
+    pub fn store(err: impl std::fmt::Display) -> Self {
+        Self::Store {
+            message: err.to_string(),
+        }
+    }
+
+    pub fn cache(message: impl std::fmt::Display) -> Self {
+        Self::Cache {
+            message: message.to_string(),
+        }
+    }
+
+    pub fn window(message: impl Into<String>) -> Self {
+        Self::Window {
+            message: message.into(),
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use crate::art_api::ImageData;
+use crate::disk_cache::{extension_for_mime, file_stem};
+use crate::error::ArtError;
+use crate::Artwork;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::{AppHandle, Wry};
+use tauri_plugin_store::{Store, StoreExt};
+
+const STORE_FILE: &str = "favorites.json";
+const STORE_KEY: &str = "favorites";
+
+/// A favorite as recorded in the store; the image is kept as a file in the favorites dir
+#[derive(Clone, Serialize, Deserialize)]
+struct FavoriteEntry {
+    artwork: Artwork,
+    image_file: String,
+    mime: String,
+}
+
+/// Starred artworks: metadata in `favorites.json`, image copies on disk.
+///
+/// Unlike the disk cache, favorites are never evicted.
+pub struct Favorites {
+    store: Arc<Store<Wry>>,
+    dir: PathBuf,
+    /// Serializes read-modify-write cycles on the store
+    lock: tokio::sync::Mutex<()>,
+}
+
+impl Favorites {
+    pub fn open(app: &AppHandle, dir: PathBuf) -> Result<Self, ArtError> {
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| ArtError::cache(format!("Failed to create {}: {e}", dir.display())))?;
+        let store = app.store(STORE_FILE).map_err(ArtError::store)?;
+        Ok(Self {
+            store,
+            dir,
+            lock: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    fn entries(&self) -> Vec<FavoriteEntry> {
+        self.store
+            .get(STORE_KEY)
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_entries(&self, entries: &[FavoriteEntry]) -> Result<(), ArtError> {
+        let value = serde_json::to_value(entries).map_err(ArtError::store)?;
+        self.store.set(STORE_KEY, value);
+        self.store.save().map_err(ArtError::store)
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.entries().iter().any(|e| e.artwork.id == id)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries().is_empty()
+    }
+
+    /// Star an artwork, keeping a copy of its image in the favorites dir
+    pub async fn add(&self, artwork: &Artwork, image: &ImageData) -> Result<(), ArtError> {
+        let _guard = self.lock.lock().await;
+        let mut entries = self.entries();
+        if entries.iter().any(|e| e.artwork.id == artwork.id) {
+            return Ok(());
+        }
+
+        let image_file = format!("{}.{}", file_stem(&artwork.id), extension_for_mime(&image.mime));
+        tokio::fs::write(self.dir.join(&image_file), &image.bytes)
+            .await
+            .map_err(|e| ArtError::cache(format!("Failed to write {image_file}: {e}")))?;
+
+        entries.push(FavoriteEntry {
+            artwork: artwork.clone(),
+            image_file,
+            mime: image.mime.clone(),
+        });
+        self.save_entries(&entries)?;
+        log::info!("Added favorite: {}", artwork.title);
+        Ok(())
+    }
+
+    /// Remove a favorite and its image copy. Returns whether it was starred.
+    pub async fn remove(&self, id: &str) -> Result<bool, ArtError> {
+        let _guard = self.lock.lock().await;
+        let mut entries = self.entries();
+        let Some(pos) = entries.iter().position(|e| e.artwork.id == id) else {
+            return Ok(false);
+        };
+
+        let entry = entries.remove(pos);
+        self.save_entries(&entries)?;
+        let _ = tokio::fs::remove_file(self.dir.join(&entry.image_file)).await;
+        log::info!("Removed favorite: {}", entry.artwork.title);
+        Ok(true)
+    }
+
+    /// All favorites, oldest first
+    pub fn list(&self) -> Vec<Artwork> {
+        self.entries().into_iter().map(|e| e.artwork).collect()
+    }
+
+    /// Read the stored image copy for a favorite
+    pub async fn image(&self, id: &str) -> Option<ImageData> {
+        let entry = self.entries().into_iter().find(|e| e.artwork.id == id)?;
+        let bytes = tokio::fs::read(self.dir.join(&entry.image_file)).await.ok()?;
+        Some(ImageData {
+            bytes,
+            mime: entry.mime,
+        })
+    }
+
+    /// Pick a random favorite, avoiding `exclude` unless nothing else is left
+    pub fn random_excluding(&self, exclude: &HashSet<String>) -> Option<Artwork> {
+        let mut entries = self.entries();
+        if entries.iter().any(|e| !exclude.contains(&e.artwork.id)) {
+            entries.retain(|e| !exclude.contains(&e.artwork.id));
+        }
+
+        if entries.is_empty() {
+            return None;
+        }
+        let index = rand::rng().random_range(0..entries.len());
+        Some(entries.swap_remove(index).artwork)
+    }
+}
@@ -1,25 +1,213 @@
+use crate::error::{ArtError, HotkeyProblem};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use tauri::AppHandle;
-use tauri_plugin_global_shortcut::GlobalShortcutExt;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+use tauri_plugin_store::StoreExt;
 
 pub const DEFAULT_HOTKEY: &str = "CmdOrCtrl+Shift+Up";
 
-pub fn register_hotkey(app: &AppHandle, shortcut: &str) -> Result<(), String> {
-    // Unregister all first
+/// Something a global shortcut can do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HotkeyAction {
+    /// Show or hide the overlays
+    Toggle,
+    Next,
+    Prev,
+    /// Star or unstar the current artwork
+    Favorite,
+    /// Open the current artwork's page on the museum website
+    OpenInBrowser,
+    PauseSlideshow,
+}
+
+impl HotkeyAction {
+    /// Name shown in error messages
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Toggle => "show/hide art",
+            Self::Next => "next artwork",
+            Self::Prev => "previous artwork",
+            Self::Favorite => "favorite",
+            Self::OpenInBrowser => "open museum page",
+            Self::PauseSlideshow => "pause slideshow",
+        }
+    }
+}
+
+/// Shortcut bound to each action, saved under `hotkeys` in `settings.json`.
+/// Actions without an entry (or with an empty one) are unbound.
+pub type Keymap = BTreeMap<HotkeyAction, String>;
+
+/// Only the toggle is bound by default; anything else could steal keys from other apps
+pub fn default_keymap() -> Keymap {
+    Keymap::from([(HotkeyAction::Toggle, DEFAULT_HOTKEY.to_string())])
+}
+
+/// The saved keymap, falling back to the single `hotkey` setting from older versions
+pub fn load_keymap(app: &AppHandle) -> Keymap {
+    let Ok(store) = app.store("settings.json") else {
+        return default_keymap();
+    };
+    if let Some(keymap) = store.get("hotkeys").and_then(|v| serde_json::from_value(v).ok()) {
+        return keymap;
+    }
+    match store.get("hotkey").and_then(|v| v.as_str().map(String::from)) {
+        Some(legacy) => Keymap::from([(HotkeyAction::Toggle, legacy)]),
+        None => default_keymap(),
+    }
+}
+
+pub fn save_keymap(app: &AppHandle, keymap: &Keymap) -> Result<(), ArtError> {
+    let store = app.store("settings.json").map_err(ArtError::store)?;
+    store.set("hotkeys", serde_json::to_value(keymap).map_err(ArtError::store)?);
+    Ok(())
+}
+
+fn hotkey_error(shortcut: &str, problem: HotkeyProblem, message: impl Into<String>) -> ArtError {
+    ArtError::Hotkey {
+        shortcut: shortcut.into(),
+        problem,
+        message: message.into(),
+    }
+}
+
+/// Parse an accelerator like "CmdOrCtrl+Shift+Up", rejecting ones that would
+/// swallow ordinary typing
+pub fn parse_shortcut(shortcut: &str) -> Result<Shortcut, ArtError> {
+    let parsed: Shortcut = shortcut
+        .parse()
+        .map_err(|e| hotkey_error(shortcut, HotkeyProblem::Invalid, format!("{e}")))?;
+
+    let key = parsed.key.to_string();
+    let function_key = key.len() > 1 && key.starts_with('F') && key[1..].chars().all(|c| c.is_ascii_digit());
+    if parsed.mods.is_empty() && !function_key {
+        return Err(hotkey_error(
+            shortcut,
+            HotkeyProblem::Invalid,
+            "add a modifier (Ctrl, Alt, Shift or Cmd) or use a function key",
+        ));
+    }
+    Ok(parsed)
+}
+
+/// Check that `shortcut` can be bound to `action`: it parses, no other action in
+/// `keymap` uses it, and no other application has grabbed it
+pub fn validate(
+    app: &AppHandle,
+    action: HotkeyAction,
+    shortcut: &str,
+    keymap: &Keymap,
+) -> Result<(), ArtError> {
+    let parsed = parse_shortcut(shortcut)?;
+
+    for (&other, other_shortcut) in keymap {
+        if other == action || other_shortcut.is_empty() {
+            continue;
+        }
+        if parse_shortcut(other_shortcut).is_ok_and(|s| s == parsed) {
+            return Err(hotkey_error(
+                shortcut,
+                HotkeyProblem::Duplicate,
+                format!("already used for {}", other.label()),
+            ));
+        }
+    }
+
+    // Ours already; re-registering it will work
+    let global_shortcut = app.global_shortcut();
+    if global_shortcut.is_registered(parsed) {
+        return Ok(());
+    }
+
+    // The only way to know if another app holds it is to try to grab it
+    global_shortcut
+        .register(parsed)
+        .map_err(|e| hotkey_error(shortcut, HotkeyProblem::Taken, e.to_string()))?;
+    let _ = global_shortcut.unregister(parsed);
+    Ok(())
+}
+
+/// Switch from `old` to `new` bindings. If any of the new shortcuts can't be
+/// registered, the old ones are put back and the error is returned.
+pub fn swap_keymap(app: &AppHandle, old: &Keymap, new: &Keymap) -> Result<(), ArtError> {
+    // Catch typos and duplicates before touching the current bindings
+    let mut seen: Vec<(HotkeyAction, Shortcut)> = Vec::new();
+    for (&action, shortcut) in new.iter().filter(|(_, s)| !s.is_empty()) {
+        let parsed = parse_shortcut(shortcut)?;
+        if let Some((other, _)) = seen.iter().find(|(_, s)| *s == parsed) {
+            return Err(hotkey_error(
+                shortcut,
+                HotkeyProblem::Duplicate,
+                format!("bound to both {} and {}", other.label(), action.label()),
+            ));
+        }
+        seen.push((action, parsed));
+    }
+
+    if let Err(e) = register_keymap(app, new) {
+        log::warn!("Restoring previous hotkeys: {e}");
+        if let Err(restore) = register_keymap(app, old) {
+            log::error!("Failed to restore previous hotkeys: {restore}");
+        }
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Replace all global shortcuts with the ones in `keymap`.
+///
+/// Every binding is attempted; the first failure is returned.
+pub fn register_keymap(app: &AppHandle, keymap: &Keymap) -> Result<(), ArtError> {
     let _ = app.global_shortcut().unregister_all();
 
+    let mut first_error = None;
+    for (&action, shortcut) in keymap {
+        if shortcut.is_empty() {
+            continue;
+        }
+        if let Err(e) = register_action(app, action, shortcut) {
+            log::error!("{e}");
+            first_error.get_or_insert(e);
+        }
+    }
+    first_error.map_or(Ok(()), Err)
+}
+
+fn register_action(app: &AppHandle, action: HotkeyAction, shortcut: &str) -> Result<(), ArtError> {
     let app_handle = app.clone();
     app.global_shortcut()
         .on_shortcut(shortcut, move |_app, _shortcut, event| {
             if event.state == tauri_plugin_global_shortcut::ShortcutState::Pressed {
-                log::info!("Hotkey pressed, toggling artwork display");
+                log::info!("Hotkey pressed: {action:?}");
                 let app = app_handle.clone();
                 tauri::async_runtime::spawn(async move {
-                    crate::commands::toggle_overlays(app).await;
+                    run_action(app, action).await;
                 });
             }
         })
-        .map_err(|e| format!("Failed to register shortcut '{shortcut}': {e}"))?;
+        .map_err(|e| hotkey_error(shortcut, HotkeyProblem::Taken, e.to_string()))?;
 
-    log::info!("Registered hotkey: {shortcut}");
+    log::info!("Registered hotkey {shortcut} for {action:?}");
     Ok(())
 }
+
+async fn run_action(app: AppHandle, action: HotkeyAction) {
+    use crate::commands;
+
+    let result = match action {
+        HotkeyAction::Toggle => {
+            commands::toggle_overlays(app).await;
+            Ok(())
+        }
+        HotkeyAction::Next => commands::step_overlays(&app, true).await,
+        HotkeyAction::Prev => commands::step_overlays(&app, false).await,
+        HotkeyAction::Favorite => commands::toggle_current_favorite(&app).await,
+        HotkeyAction::OpenInBrowser => commands::open_current_page(&app).await,
+        HotkeyAction::PauseSlideshow => commands::toggle_slideshow(&app).map(|_| ()),
+    };
+    if let Err(e) = result {
+        log::warn!("Hotkey action {action:?} failed: {e}");
+    }
+}
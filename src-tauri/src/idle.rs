@@ -0,0 +1,426 @@
+use crate::error::ArtError;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+use tokio::process::Command;
+
+const STORE_KEY: &str = "screensaver";
+
+/// How often the session idle time is polled
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Screensaver preferences saved in `settings.json`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ScreensaverSettings {
+    pub enabled: bool,
+    pub idle_minutes: u64,
+}
+
+impl Default for ScreensaverSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            idle_minutes: 10,
+        }
+    }
+}
+
+impl ScreensaverSettings {
+    pub fn load(app: &AppHandle) -> Self {
+        app.store("settings.json")
+            .ok()
+            .and_then(|store| store.get(STORE_KEY))
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, app: &AppHandle) -> Result<(), ArtError> {
+        if self.idle_minutes == 0 {
+            return Err(ArtError::invalid("Idle time must be at least 1 minute"));
+        }
+        let store = app.store("settings.json").map_err(ArtError::store)?;
+        store.set(STORE_KEY, serde_json::to_value(self).map_err(ArtError::store)?);
+        Ok(())
+    }
+}
+
+/// Something that can tell how long the user has been inactive
+#[async_trait]
+pub trait IdleSource: Send + Sync {
+    /// Short name used in logs
+    fn name(&self) -> &str;
+
+    /// Time since the last keyboard or mouse input, or `None` if it can't be determined
+    async fn idle_time(&self) -> Option<Duration>;
+}
+
+/// X11: `xprintidle` prints the idle time in milliseconds
+pub struct XPrintIdle;
+
+#[async_trait]
+impl IdleSource for XPrintIdle {
+    fn name(&self) -> &str {
+        "xprintidle"
+    }
+
+    async fn idle_time(&self) -> Option<Duration> {
+        let out = run("xprintidle", &[]).await?;
+        out.trim().parse().ok().map(Duration::from_millis)
+    }
+}
+
+/// GNOME (X11 and Wayland): Mutter's IdleMonitor D-Bus interface, in milliseconds
+pub struct MutterIdleMonitor;
+
+#[async_trait]
+impl IdleSource for MutterIdleMonitor {
+    fn name(&self) -> &str {
+        "Mutter IdleMonitor"
+    }
+
+    async fn idle_time(&self) -> Option<Duration> {
+        let out = run(
+            "gdbus",
+            &[
+                "call",
+                "--session",
+                "--dest",
+                "org.gnome.Mutter.IdleMonitor",
+                "--object-path",
+                "/org/gnome/Mutter/IdleMonitor/Core",
+                "--method",
+                "org.gnome.Mutter.IdleMonitor.GetIdletime",
+            ],
+        )
+        .await?;
+        // Output looks like "(uint64 12345,)"
+        parse_gdbus_number(&out).map(Duration::from_millis)
+    }
+}
+
+/// KDE and other Wayland desktops: the freedesktop ScreenSaver interface, in seconds
+pub struct FreedesktopScreenSaver;
+
+#[async_trait]
+impl IdleSource for FreedesktopScreenSaver {
+    fn name(&self) -> &str {
+        "org.freedesktop.ScreenSaver"
+    }
+
+    async fn idle_time(&self) -> Option<Duration> {
+        let out = run(
+            "gdbus",
+            &[
+                "call",
+                "--session",
+                "--dest",
+                "org.freedesktop.ScreenSaver",
+                "--object-path",
+                "/org/freedesktop/ScreenSaver",
+                "--method",
+                "org.freedesktop.ScreenSaver.GetSessionIdleTime",
+            ],
+        )
+        .await?;
+        parse_gdbus_number(&out).map(Duration::from_secs)
+    }
+}
+
+/// macOS: `HIDIdleTime` from the IOHIDSystem registry entry, in nanoseconds
+pub struct MacHidIdle;
+
+#[async_trait]
+impl IdleSource for MacHidIdle {
+    fn name(&self) -> &str {
+        "IOHIDSystem"
+    }
+
+    async fn idle_time(&self) -> Option<Duration> {
+        let out = run("ioreg", &["-c", "IOHIDSystem", "-d", "4"]).await?;
+        let line = out.lines().find(|l| l.contains("\"HIDIdleTime\""))?;
+        let nanos = line.rsplit('=').next()?.trim().parse().ok()?;
+        Some(Duration::from_nanos(nanos))
+    }
+}
+
+/// Tries each source in turn and sticks with the first one that answers
+pub struct FirstAvailable {
+    sources: Vec<Box<dyn IdleSource>>,
+    chosen: std::sync::Mutex<Option<usize>>,
+}
+
+impl FirstAvailable {
+    pub fn new(sources: Vec<Box<dyn IdleSource>>) -> Self {
+        Self {
+            sources,
+            chosen: std::sync::Mutex::new(None),
+        }
+    }
+}
+
+#[async_trait]
+impl IdleSource for FirstAvailable {
+    fn name(&self) -> &str {
+        let chosen = *self.chosen.lock().unwrap();
+        chosen.map_or("none", |i| self.sources[i].name())
+    }
+
+    async fn idle_time(&self) -> Option<Duration> {
+        let chosen = *self.chosen.lock().unwrap();
+        if let Some(i) = chosen {
+            return self.sources[i].idle_time().await;
+        }
+        for (i, source) in self.sources.iter().enumerate() {
+            if let Some(idle) = source.idle_time().await {
+                log::info!("Using {} for idle detection", source.name());
+                *self.chosen.lock().unwrap() = Some(i);
+                return Some(idle);
+            }
+        }
+        None
+    }
+}
+
+/// The idle source for the current platform and session
+pub fn system_source() -> Box<dyn IdleSource> {
+    let sources: Vec<Box<dyn IdleSource>> = if cfg!(target_os = "macos") {
+        vec![Box::new(MacHidIdle)]
+    } else if cfg!(windows) {
+        // Not implemented yet; the screensaver setting has no effect
+        Vec::new()
+    } else if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        // Wayland has no universal idle query; ask the compositor over D-Bus
+        vec![Box::new(MutterIdleMonitor), Box::new(FreedesktopScreenSaver)]
+    } else {
+        vec![
+            Box::new(XPrintIdle),
+            Box::new(MutterIdleMonitor),
+            Box::new(FreedesktopScreenSaver),
+        ]
+    };
+    Box::new(FirstAvailable::new(sources))
+}
+
+async fn run(program: &str, args: &[&str]) -> Option<String> {
+    let out = Command::new(program).args(args).output().await.ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    String::from_utf8(out.stdout).ok()
+}
+
+/// Pull the number out of a gdbus reply like "(uint64 12345,)" or "(uint32 12,)"
+fn parse_gdbus_number(out: &str) -> Option<u64> {
+    out.split_whitespace()
+        .nth(1)?
+        .trim_end_matches([',', ')'])
+        .parse()
+        .ok()
+}
+
+/// What the screensaver should do after an idle-time reading
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdleAction {
+    ShowArt,
+    Dismiss,
+}
+
+/// Decides when to show and dismiss the overlays, given successive idle readings.
+///
+/// Only overlays this trigger opened are dismissed; ones opened by the hotkey or
+/// tray are left alone. Art is shown at most once per idle period, so a failed
+/// attempt (offline with nothing cached, say) isn't retried until the user is back.
+#[derive(Debug, Default)]
+pub struct IdleTrigger {
+    /// Whether the overlays currently open were opened by us
+    showing: bool,
+    /// Whether we've already shown art since the last input
+    fired: bool,
+    last_idle: Duration,
+}
+
+impl IdleTrigger {
+    pub fn update(&mut self, idle: Duration, threshold: Duration, overlays_open: bool) -> Option<IdleAction> {
+        let input_since_last = idle < self.last_idle;
+        self.last_idle = idle;
+        if input_since_last {
+            self.fired = false;
+        }
+
+        if self.showing {
+            if !overlays_open {
+                // Closed some other way, or never opened
+                self.showing = false;
+            } else if input_since_last {
+                self.showing = false;
+                return Some(IdleAction::Dismiss);
+            }
+            None
+        } else if !self.fired && !overlays_open && idle >= threshold {
+            self.showing = true;
+            self.fired = true;
+            Some(IdleAction::ShowArt)
+        } else {
+            None
+        }
+    }
+}
+
+/// Polls an idle source and turns its readings into screensaver actions
+pub struct IdleWatcher {
+    source: Box<dyn IdleSource>,
+    trigger: IdleTrigger,
+    warned: bool,
+}
+
+impl IdleWatcher {
+    pub fn new(source: Box<dyn IdleSource>) -> Self {
+        Self {
+            source,
+            trigger: IdleTrigger::default(),
+            warned: false,
+        }
+    }
+
+    /// Take one reading. While the screensaver is off the source isn't queried
+    /// and the trigger starts over.
+    pub async fn poll(
+        &mut self,
+        settings: ScreensaverSettings,
+        overlays_open: bool,
+    ) -> Option<IdleAction> {
+        if !settings.enabled {
+            self.trigger = IdleTrigger::default();
+            return None;
+        }
+
+        let Some(idle) = self.source.idle_time().await else {
+            if !self.warned {
+                log::warn!("Screensaver enabled but idle time is unavailable on this system");
+                self.warned = true;
+            }
+            return None;
+        };
+
+        let threshold = Duration::from_secs(settings.idle_minutes * 60);
+        self.trigger.update(idle, threshold, overlays_open)
+    }
+}
+
+/// Poll `source` forever, showing art once the user has been idle long enough
+pub fn start_watcher(app: AppHandle, source: Box<dyn IdleSource>) {
+    tauri::async_runtime::spawn(async move {
+        let mut watcher = IdleWatcher::new(source);
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            let settings = ScreensaverSettings::load(&app);
+            match watcher.poll(settings, crate::windows::overlays_open(&app)).await {
+                Some(IdleAction::ShowArt) => {
+                    log::info!("Idle for {} minutes, showing art", settings.idle_minutes);
+                    crate::commands::show_art(app.clone()).await;
+                }
+                Some(IdleAction::Dismiss) => {
+                    log::info!("Input detected, dismissing screensaver");
+                    crate::windows::close_overlay_windows(&app);
+                }
+                None => {}
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    /// Replays a fixed series of idle times, in minutes
+    struct Scripted(Mutex<VecDeque<u64>>);
+
+    impl Scripted {
+        fn new(minutes: &[u64]) -> Box<Self> {
+            Box::new(Self(Mutex::new(minutes.iter().copied().collect())))
+        }
+    }
+
+    #[async_trait]
+    impl IdleSource for Scripted {
+        fn name(&self) -> &str {
+            "scripted"
+        }
+
+        async fn idle_time(&self) -> Option<Duration> {
+            let minutes = self.0.lock().unwrap().pop_front()?;
+            Some(Duration::from_secs(minutes * 60))
+        }
+    }
+
+    const ON: ScreensaverSettings = ScreensaverSettings {
+        enabled: true,
+        idle_minutes: 10,
+    };
+
+    /// Poll until the script runs out, opening and closing the overlays as told.
+    /// With `show_fails`, ShowArt leaves the overlays closed.
+    async fn run(
+        minutes: &[u64],
+        settings: ScreensaverSettings,
+        show_fails: bool,
+    ) -> Vec<Option<IdleAction>> {
+        let mut watcher = IdleWatcher::new(Scripted::new(minutes));
+        let mut open = false;
+        let mut actions = Vec::new();
+        for _ in minutes {
+            let action = watcher.poll(settings, open).await;
+            match action {
+                Some(IdleAction::ShowArt) => open = !show_fails,
+                Some(IdleAction::Dismiss) => open = false,
+                None => {}
+            }
+            actions.push(action);
+        }
+        actions
+    }
+
+    #[tokio::test]
+    async fn shows_art_once_the_threshold_is_crossed() {
+        let actions = run(&[1, 5, 9, 10, 11], ON, false).await;
+        assert_eq!(actions, [None, None, None, Some(IdleAction::ShowArt), None]);
+    }
+
+    #[tokio::test]
+    async fn dismisses_on_input_and_rearms() {
+        let actions = run(&[10, 11, 0, 4, 10], ON, false).await;
+        assert_eq!(
+            actions,
+            [
+                Some(IdleAction::ShowArt),
+                None,
+                Some(IdleAction::Dismiss),
+                None,
+                Some(IdleAction::ShowArt),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_a_failed_show_until_input() {
+        let actions = run(&[10, 11, 12, 13, 0, 10], ON, true).await;
+        assert_eq!(
+            actions,
+            [Some(IdleAction::ShowArt), None, None, None, None, Some(IdleAction::ShowArt)]
+        );
+    }
+
+    #[tokio::test]
+    async fn does_nothing_while_disabled() {
+        let off = ScreensaverSettings {
+            enabled: false,
+            ..ON
+        };
+        assert_eq!(run(&[5, 10, 30], off, false).await, [None, None, None]);
+    }
+}
@@ -1,7 +1,15 @@
-mod art_api;
+pub mod art_api;
 mod art_cache;
 mod commands;
+mod disk_cache;
+pub mod error;
+mod favorites;
 mod hotkey;
+mod idle;
+mod protocol;
+mod save;
+mod slideshow;
+mod wallpaper;
 mod windows;
 
 use serde::{Deserialize, Serialize};
@@ -15,11 +23,41 @@ pub struct Artwork {
     pub date: String,
     pub medium: String,
     pub source: String,
-    pub image_base64: String,
+    /// `art://` URL the overlay loads the image from (see `protocol::image_url`)
+    pub image_url: String,
+    /// Size of the source image, or at least its proportions, when the source reports it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_size: Option<art_api::ImageSize>,
+    /// How the museum acquired the work, e.g. "Gift of ..., 1929"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credit_line: Option<String>,
+    /// Physical dimensions as the museum writes them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub department: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub culture: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accession_number: Option<String>,
+    /// Whether the museum marks the image as public domain / CC0
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_domain: Option<bool>,
+    /// License name as the source gives it, e.g. "CC BY-SA 4.0"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// The artwork's page on the museum website
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object_url: Option<String>,
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    run_with_sources(art_api::SourceRegistry::with_builtin_sources());
+}
+
+/// Run the app drawing artwork from a custom set of sources
+pub fn run_with_sources(sources: art_api::SourceRegistry) {
     tauri::Builder::default()
         .plugin(
             tauri_plugin_log::Builder::default()
@@ -28,15 +66,42 @@ pub fn run() {
         )
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .plugin(tauri_plugin_store::Builder::new().build())
-        .manage(art_cache::ArtCache::new())
+        .plugin(tauri_plugin_opener::init())
+        .register_asynchronous_uri_scheme_protocol(protocol::SCHEME, protocol::handle)
+        .manage(slideshow::Slideshow::default())
         .invoke_handler(tauri::generate_handler![
             commands::get_current_artwork,
             commands::next_artwork,
             commands::prev_artwork,
+            commands::get_span_slice,
+            commands::is_offline,
             commands::overlay_ready,
             commands::dismiss_overlays,
-            commands::get_hotkey,
-            commands::set_hotkey,
+            commands::favorite_artwork,
+            commands::unfavorite_artwork,
+            commands::list_favorites,
+            commands::is_favorite,
+            commands::open_artwork_page,
+            commands::save_artwork,
+            commands::get_save_dir,
+            commands::set_save_dir,
+            commands::set_as_wallpaper,
+            commands::get_wallpaper,
+            commands::set_wallpaper,
+            commands::get_favorites_only,
+            commands::set_favorites_only,
+            commands::get_sources,
+            commands::set_sources,
+            commands::get_display_mode,
+            commands::set_display_mode,
+            commands::get_slideshow,
+            commands::set_slideshow,
+            commands::toggle_slideshow_pause,
+            commands::get_screensaver,
+            commands::set_screensaver,
+            commands::get_hotkeys,
+            commands::validate_hotkey,
+            commands::set_hotkeys,
         ])
         .setup(|app| {
             // Set accessory activation policy (no dock icon)
@@ -49,22 +114,73 @@ pub fn run() {
             // Set up tray
             setup_tray(app.handle())?;
 
-            // Register global hotkey
-            let handle = app.handle().clone();
+            // Register global hotkeys
             let store = tauri_plugin_store::StoreExt::store(app.handle(), "settings.json")
                 .expect("Failed to open store");
-            let shortcut = store
-                .get("hotkey")
-                .and_then(|v| v.as_str().map(String::from))
-                .unwrap_or_else(|| hotkey::DEFAULT_HOTKEY.to_string());
-
-            if let Err(e) = hotkey::register_hotkey(&handle, &shortcut) {
-                log::error!("Failed to register hotkey: {e}");
+            if let Err(e) = hotkey::register_keymap(app.handle(), &hotkey::load_keymap(app.handle())) {
+                log::error!("Failed to register hotkeys: {e}");
             }
 
+            // Persist downloaded artwork under the app data dir so restarts start warm
+            let data_dir = app
+                .path()
+                .app_data_dir()
+                .map_err(|e| error::ArtError::cache(e.to_string()));
+            // Images are served to the overlay from this cache, so fall back to a temp dir
+            let disk = data_dir.clone().and_then(|dir| {
+                disk_cache::DiskCache::open(dir.join("artwork-cache"), disk_cache::DEFAULT_MAX_BYTES)
+            });
+            let disk = match disk {
+                Ok(disk) => disk,
+                Err(e) => {
+                    log::error!("Disk cache unavailable, using temp dir: {e}");
+                    disk_cache::DiskCache::open(
+                        std::env::temp_dir().join("art-tauri-cache"),
+                        disk_cache::DEFAULT_MAX_BYTES,
+                    )?
+                }
+            };
+
+            let wallpaper_dir = match &data_dir {
+                Ok(dir) => dir.join("wallpaper"),
+                Err(_) => std::env::temp_dir().join("art-tauri-wallpaper"),
+            };
+            app.manage(wallpaper::Wallpaper::new(wallpaper::system_backend(), wallpaper_dir));
+
+            let favorites = data_dir
+                .and_then(|dir| favorites::Favorites::open(app.handle(), dir.join("favorites")));
+            let favorites = match favorites {
+                Ok(favorites) => Some(favorites),
+                Err(e) => {
+                    log::error!("Favorites unavailable: {e}");
+                    None
+                }
+            };
+
             // Start background prefetch
-            let cache = app.state::<art_cache::ArtCache>();
+            let cache = art_cache::ArtCache::new(sources, disk, favorites);
+            cache.set_favorites_only(
+                store
+                    .get("favorites_only")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false),
+            );
+            let source_settings = store
+                .get("sources")
+                .and_then(|v| serde_json::from_value(v).ok())
+                .unwrap_or_default();
+            cache.set_source_settings(source_settings);
+            if let Some(size) = windows::monitor_target_size(app.handle()) {
+                cache.set_target_size(size);
+            }
             cache.start_prefetch();
+            app.manage(cache);
+
+            // Show art after a stretch of inactivity, if enabled in settings
+            idle::start_watcher(app.handle().clone(), idle::system_source());
+
+            // Change the desktop background daily, if enabled in settings
+            wallpaper::start_rotation(app.handle().clone());
 
             Ok(())
         })
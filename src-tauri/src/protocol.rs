@@ -0,0 +1,69 @@
+use crate::art_cache::ArtCache;
+use crate::disk_cache::fnv1a;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, NON_ALPHANUMERIC};
+use tauri::http::{header, Request, Response, StatusCode};
+use tauri::{AppHandle, Manager, UriSchemeContext, UriSchemeResponder, Wry};
+
+/// Custom URI scheme the overlay loads artwork images from
+pub const SCHEME: &str = "art";
+
+/// URL the webview can load an artwork's image from.
+///
+/// Custom schemes are exposed as `art://localhost/...` on macOS and Linux, and as
+/// `http://art.localhost/...` on Windows and Android. The query holds a hash of
+/// the image, so a re-download at another size (or an edited local file) gets a
+/// new URL instead of the webview's cached copy.
+pub fn image_url(id: &str, bytes: &[u8]) -> String {
+    let id = utf8_percent_encode(id, NON_ALPHANUMERIC);
+    let version = fnv1a(bytes);
+    if cfg!(any(windows, target_os = "android")) {
+        format!("http://{SCHEME}.localhost/image/{id}?v={version:x}")
+    } else {
+        format!("{SCHEME}://localhost/image/{id}?v={version:x}")
+    }
+}
+
+/// Handle an `art://` request by streaming the image bytes from the cache or favorites
+pub fn handle(ctx: UriSchemeContext<'_, Wry>, request: Request<Vec<u8>>, responder: UriSchemeResponder) {
+    let app = ctx.app_handle().clone();
+    tauri::async_runtime::spawn(async move {
+        responder.respond(serve(&app, &request).await);
+    });
+}
+
+async fn serve(app: &AppHandle, request: &Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let Some(id) = request
+        .uri()
+        .path()
+        .strip_prefix("/image/")
+        .and_then(|id| percent_decode_str(id).decode_utf8().ok())
+    else {
+        return error_response(StatusCode::BAD_REQUEST, "expected /image/<id>");
+    };
+
+    let Some(cache) = app.try_state::<ArtCache>() else {
+        return error_response(StatusCode::SERVICE_UNAVAILABLE, "artwork cache not ready");
+    };
+
+    match cache.image(&id).await {
+        Some(image) => Response::builder()
+            .header(header::CONTENT_TYPE, image.mime)
+            // The URL changes with the image, so the webview may cache it forever
+            .header(header::CACHE_CONTROL, "max-age=31536000, immutable")
+            .body(image.bytes)
+            .unwrap_or_else(|_| error_response(StatusCode::INTERNAL_SERVER_ERROR, "bad response")),
+        None => {
+            log::warn!("No cached image for {id}");
+            error_response(StatusCode::NOT_FOUND, "image not found")
+        }
+    }
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response<Vec<u8>> {
+    let mut response = Response::new(message.as_bytes().to_vec());
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(header::CONTENT_TYPE, header::HeaderValue::from_static("text/plain"));
+    response
+}
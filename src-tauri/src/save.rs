@@ -0,0 +1,167 @@
+use crate::art_api::ImageData;
+use crate::disk_cache::extension_for_mime;
+use crate::error::ArtError;
+use crate::Artwork;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+/// Metadata written next to a saved image as `<name>.json`
+#[derive(Serialize)]
+struct Sidecar<'a> {
+    id: &'a str,
+    title: &'a str,
+    artist: &'a str,
+    date: &'a str,
+    medium: &'a str,
+    source: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    credit_line: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    public_domain: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    license: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    object_url: Option<&'a str>,
+}
+
+/// Folder artwork is saved to: the `save_dir` setting, or an "Art" folder in Pictures
+pub fn save_dir(app: &AppHandle) -> Result<PathBuf, ArtError> {
+    let configured = app
+        .store("settings.json")
+        .ok()
+        .and_then(|store| store.get("save_dir"))
+        .and_then(|v| v.as_str().map(PathBuf::from))
+        .filter(|p| !p.as_os_str().is_empty());
+    if let Some(dir) = configured {
+        return Ok(dir);
+    }
+    app.path()
+        .picture_dir()
+        .map(|dir| dir.join("Art"))
+        .map_err(|e| ArtError::unsupported(format!("No Pictures folder: {e}")))
+}
+
+/// Write an artwork's displayed image (the screen-sized download) to `dir` as
+/// "Artist - Title.ext", with a JSON sidecar holding its metadata. Returns the image path.
+pub async fn save_artwork(artwork: &Artwork, image: &ImageData, dir: &Path) -> Result<PathBuf, ArtError> {
+    tokio::fs::create_dir_all(dir)
+        .await
+        .map_err(|e| ArtError::cache(format!("Failed to create {}: {e}", dir.display())))?;
+
+    let base = file_name(&format!("{} - {}", artwork.artist, artwork.title));
+    let ext = extension_for_mime(&image.mime);
+    let stem = unused_stem(dir, &base, ext).await;
+    let image_path = dir.join(format!("{stem}.{ext}"));
+
+    tokio::fs::write(&image_path, &image.bytes)
+        .await
+        .map_err(|e| ArtError::cache(format!("Failed to write {}: {e}", image_path.display())))?;
+
+    let sidecar = Sidecar {
+        id: &artwork.id,
+        title: &artwork.title,
+        artist: &artwork.artist,
+        date: &artwork.date,
+        medium: &artwork.medium,
+        source: &artwork.source,
+        credit_line: artwork.credit_line.as_deref(),
+        public_domain: artwork.public_domain,
+        license: artwork.license.as_deref(),
+        object_url: artwork.object_url.as_deref(),
+    };
+    let json = serde_json::to_vec_pretty(&sidecar).map_err(ArtError::cache)?;
+    let sidecar_path = dir.join(format!("{stem}.json"));
+    if let Err(e) = tokio::fs::write(&sidecar_path, json).await {
+        log::warn!("Failed to write {}: {e}", sidecar_path.display());
+    }
+
+    log::info!("Saved {} to {}", artwork.title, image_path.display());
+    Ok(image_path)
+}
+
+/// Make a title safe to use as a file name on every platform
+fn file_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .take(120)
+        .collect();
+    let trimmed = cleaned.trim().trim_end_matches('.');
+    if trimmed.is_empty() {
+        "Artwork".into()
+    } else {
+        trimmed.into()
+    }
+}
+
+/// `base`, or `base (2)`, `base (3)`... so existing files are never overwritten
+async fn unused_stem(dir: &Path, base: &str, ext: &str) -> String {
+    let mut stem = base.to_string();
+    let mut n = 2;
+    while tokio::fs::try_exists(dir.join(format!("{stem}.{ext}")))
+        .await
+        .unwrap_or(false)
+    {
+        stem = format!("{base} ({n})");
+        n += 1;
+    }
+    stem
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::art_cache::tests::artwork;
+
+    #[test]
+    fn file_names_replace_reserved_characters() {
+        assert_eq!(file_name("Monet - Water Lilies"), "Monet - Water Lilies");
+        assert_eq!(file_name("A/B: \"C\"?"), "A_B_ _C__");
+        assert_eq!(file_name("Untitled..."), "Untitled");
+        assert_eq!(file_name(" .. "), "Artwork");
+        assert_eq!(file_name(&"x".repeat(300)).len(), 120);
+    }
+
+    #[tokio::test]
+    async fn saving_twice_keeps_both_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let image = ImageData {
+            bytes: vec![1, 2, 3],
+            mime: "image/jpeg".into(),
+        };
+        let first = save_artwork(&artwork("t-1"), &image, dir.path()).await.unwrap();
+        let second = save_artwork(&artwork("t-1"), &image, dir.path()).await.unwrap();
+
+        assert_eq!(first, dir.path().join("Someone - Cached.jpg"));
+        assert_eq!(second, dir.path().join("Someone - Cached (2).jpg"));
+        assert!(dir.path().join("Someone - Cached (2).json").exists());
+        assert_eq!(std::fs::read(&first).unwrap(), image.bytes);
+    }
+
+    #[tokio::test]
+    async fn sidecar_holds_the_artwork_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut art = artwork("t-1");
+        art.object_url = Some("https://example.org/t-1".into());
+        let image = ImageData {
+            bytes: vec![0; 4],
+            mime: "image/png".into(),
+        };
+        save_artwork(&art, &image, dir.path()).await.unwrap();
+
+        let json = std::fs::read(dir.path().join("Someone - Cached.json")).unwrap();
+        let sidecar: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(sidecar["id"], "t-1");
+        assert_eq!(sidecar["title"], "Cached");
+        assert_eq!(sidecar["artist"], "Someone");
+        assert_eq!(sidecar["source"], "Test");
+        assert_eq!(sidecar["object_url"], "https://example.org/t-1");
+        assert!(sidecar.get("license").is_none());
+    }
+}
@@ -0,0 +1,134 @@
+use crate::error::ArtError;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::async_runtime::JoinHandle;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+const STORE_KEY: &str = "slideshow";
+
+/// Shortest interval we accept, so the slideshow can't hammer the museum APIs
+pub const MIN_INTERVAL_SECS: u64 = 5;
+
+/// How often the slideshow task checks whether it's time to advance
+const TICK: Duration = Duration::from_secs(1);
+
+/// Slideshow preferences saved in `settings.json`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SlideshowSettings {
+    pub enabled: bool,
+    pub interval_secs: u64,
+}
+
+impl Default for SlideshowSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: 300,
+        }
+    }
+}
+
+impl SlideshowSettings {
+    pub fn load(app: &AppHandle) -> Self {
+        app.store("settings.json")
+            .ok()
+            .and_then(|store| store.get(STORE_KEY))
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, app: &AppHandle) -> Result<(), ArtError> {
+        if self.interval_secs < MIN_INTERVAL_SECS {
+            return Err(ArtError::invalid(format!(
+                "Slideshow interval must be at least {MIN_INTERVAL_SECS} seconds"
+            )));
+        }
+        let store = app.store("settings.json").map_err(ArtError::store)?;
+        store.set(STORE_KEY, serde_json::to_value(self).map_err(ArtError::store)?);
+        Ok(())
+    }
+}
+
+/// Background task that advances the artwork on a timer while the overlays are open.
+///
+/// Any user interaction (navigating, starring) restarts the countdown, and the
+/// slideshow can be paused outright until the overlays close.
+#[derive(Default)]
+pub struct Slideshow {
+    task: Mutex<Option<JoinHandle<()>>>,
+    paused: Arc<AtomicBool>,
+    /// When the artwork last changed, by the timer or by the user
+    last_change: Arc<Mutex<Option<Instant>>>,
+}
+
+impl Slideshow {
+    /// Start advancing if the slideshow is enabled, replacing any running task
+    pub fn start(&self, app: &AppHandle) {
+        self.stop();
+        let settings = SlideshowSettings::load(app);
+        if !settings.enabled {
+            return;
+        }
+
+        let interval = Duration::from_secs(settings.interval_secs.max(MIN_INTERVAL_SECS));
+        let paused = self.paused.clone();
+        let last_change = self.last_change.clone();
+        *last_change.lock().unwrap() = Some(Instant::now());
+
+        let app = app.clone();
+        let task = tauri::async_runtime::spawn(async move {
+            loop {
+                tokio::time::sleep(TICK).await;
+                let due = last_change
+                    .lock()
+                    .unwrap()
+                    .is_some_and(|t| t.elapsed() >= interval);
+                if paused.load(Ordering::Relaxed) || !due {
+                    continue;
+                }
+
+                *last_change.lock().unwrap() = Some(Instant::now());
+                if let Err(e) = crate::commands::advance_overlays(&app).await {
+                    log::warn!("Slideshow failed to advance: {e}");
+                }
+            }
+        });
+        *self.task.lock().unwrap() = Some(task);
+        log::info!("Slideshow started ({}s interval)", interval.as_secs());
+    }
+
+    /// Stop the task and clear the paused state
+    pub fn stop(&self) {
+        if let Some(task) = self.task.lock().unwrap().take() {
+            task.abort();
+            log::info!("Slideshow stopped");
+        }
+        self.paused.store(false, Ordering::Relaxed);
+        *self.last_change.lock().unwrap() = None;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.task.lock().unwrap().is_some()
+    }
+
+    /// Restart the countdown after the user changed the artwork themselves
+    pub fn touch(&self) {
+        if let Some(t) = self.last_change.lock().unwrap().as_mut() {
+            *t = Instant::now();
+        }
+    }
+
+    /// Pause or resume advancing. Returns whether the slideshow is now paused.
+    pub fn toggle_pause(&self) -> Result<bool, ArtError> {
+        if !self.is_running() {
+            return Err(ArtError::unsupported("Slideshow is off"));
+        }
+        let paused = !self.paused.fetch_xor(true, Ordering::Relaxed);
+        self.touch();
+        log::info!("Slideshow {}", if paused { "paused" } else { "resumed" });
+        Ok(paused)
+    }
+}
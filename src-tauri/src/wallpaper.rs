@@ -0,0 +1,466 @@
+use crate::art_api::ImageData;
+use crate::art_cache::ArtCache;
+use crate::disk_cache::{extension_for_mime, file_stem};
+use crate::error::ArtError;
+use crate::Artwork;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+use tokio::process::Command;
+
+const STORE_KEY: &str = "wallpaper";
+
+/// When the rotation last changed the wallpaper, in seconds since the epoch
+const ROTATED_AT_KEY: &str = "wallpaper_rotated_at";
+
+/// History the daily rotation draws from, kept apart from the overlays'
+pub const WALLPAPER_HISTORY: &str = "wallpaper";
+
+const ROTATE_EVERY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How often the rotation task checks whether a day has passed
+const ROTATION_CHECK: Duration = Duration::from_secs(60);
+
+/// Wallpaper preferences saved in `settings.json`
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct WallpaperSettings {
+    pub rotate_daily: bool,
+}
+
+impl WallpaperSettings {
+    pub fn load(app: &AppHandle) -> Self {
+        app.store("settings.json")
+            .ok()
+            .and_then(|store| store.get(STORE_KEY))
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, app: &AppHandle) -> Result<(), ArtError> {
+        let store = app.store("settings.json").map_err(ArtError::store)?;
+        store.set(STORE_KEY, serde_json::to_value(self).map_err(ArtError::store)?);
+        Ok(())
+    }
+}
+
+/// Something that can set the desktop background to an image file
+#[async_trait]
+pub trait WallpaperBackend: Send + Sync {
+    /// Short name used in logs
+    fn name(&self) -> &str;
+
+    async fn apply(&self, path: &Path) -> Result<(), ArtError>;
+}
+
+/// GNOME, Budgie and friends: the `org.gnome.desktop.background` keys
+pub struct Gsettings;
+
+#[async_trait]
+impl WallpaperBackend for Gsettings {
+    fn name(&self) -> &str {
+        "gsettings"
+    }
+
+    async fn apply(&self, path: &Path) -> Result<(), ArtError> {
+        let uri = tauri::Url::from_file_path(path)
+            .map_err(|_| ArtError::unsupported(format!("Not an absolute path: {}", path.display())))?;
+        // Dark mode reads its own key
+        for key in ["picture-uri", "picture-uri-dark"] {
+            let result = run(
+                "gsettings",
+                &["set", "org.gnome.desktop.background", key, uri.as_str()],
+            )
+            .await;
+            // Older GNOME has no picture-uri-dark
+            if key == "picture-uri" {
+                result?;
+            }
+        }
+        run("gsettings", &["set", "org.gnome.desktop.background", "picture-options", "zoom"]).await
+    }
+}
+
+/// KDE Plasma 5.26+
+pub struct Plasma;
+
+#[async_trait]
+impl WallpaperBackend for Plasma {
+    fn name(&self) -> &str {
+        "plasma-apply-wallpaperimage"
+    }
+
+    async fn apply(&self, path: &Path) -> Result<(), ArtError> {
+        run("plasma-apply-wallpaperimage", &[&path.to_string_lossy()]).await
+    }
+}
+
+/// X11 window managers without a desktop of their own
+pub struct Feh;
+
+#[async_trait]
+impl WallpaperBackend for Feh {
+    fn name(&self) -> &str {
+        "feh"
+    }
+
+    async fn apply(&self, path: &Path) -> Result<(), ArtError> {
+        run("feh", &["--bg-fill", &path.to_string_lossy()]).await
+    }
+}
+
+/// wlroots compositors. swaybg keeps running to draw the background, so it is
+/// left running when the app exits and only the previous instance is killed.
+#[derive(Default)]
+pub struct Swaybg {
+    child: tokio::sync::Mutex<Option<tokio::process::Child>>,
+}
+
+#[async_trait]
+impl WallpaperBackend for Swaybg {
+    fn name(&self) -> &str {
+        "swaybg"
+    }
+
+    async fn apply(&self, path: &Path) -> Result<(), ArtError> {
+        let child = Command::new("swaybg")
+            .args(["-m", "fill", "-i"])
+            .arg(path)
+            .spawn()
+            .map_err(|e| ArtError::unsupported(format!("Failed to run swaybg: {e}")))?;
+        let previous = self.child.lock().await.replace(child);
+        if let Some(mut previous) = previous {
+            if let Err(e) = previous.kill().await {
+                log::warn!("Failed to stop the previous swaybg: {e}");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub struct MacDesktop;
+
+#[cfg(target_os = "macos")]
+#[async_trait]
+impl WallpaperBackend for MacDesktop {
+    fn name(&self) -> &str {
+        "System Events"
+    }
+
+    async fn apply(&self, path: &Path) -> Result<(), ArtError> {
+        let script = format!(
+            "tell application \"System Events\" to tell every desktop to set picture to {:?}",
+            path.display().to_string()
+        );
+        run("osascript", &["-e", &script]).await
+    }
+}
+
+#[cfg(windows)]
+pub struct WindowsDesktop;
+
+#[cfg(windows)]
+#[async_trait]
+impl WallpaperBackend for WindowsDesktop {
+    fn name(&self) -> &str {
+        "SystemParametersInfo"
+    }
+
+    async fn apply(&self, path: &Path) -> Result<(), ArtError> {
+        // SPI_SETDESKWALLPAPER, persisted and broadcast
+        let script = format!(
+            "Add-Type -Namespace Art -Name Desktop -MemberDefinition '[DllImport(\"user32.dll\", CharSet = CharSet.Unicode)] public static extern int SystemParametersInfo(int a, int b, string c, int d);'; \
+             if ([Art.Desktop]::SystemParametersInfo(20, 0, '{}', 3) -eq 0) {{ exit 1 }}",
+            path.display().to_string().replace('\'', "''")
+        );
+        run("powershell", &["-NoProfile", "-NonInteractive", "-Command", &script]).await
+    }
+}
+
+/// The wallpaper backend for the current platform and desktop
+pub fn system_backend() -> Box<dyn WallpaperBackend> {
+    #[cfg(target_os = "macos")]
+    return Box::new(MacDesktop);
+
+    #[cfg(windows)]
+    return Box::new(WindowsDesktop);
+
+    #[cfg(not(any(target_os = "macos", windows)))]
+    {
+        let desktop = std::env::var("XDG_CURRENT_DESKTOP")
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if desktop.contains("kde") {
+            Box::new(Plasma)
+        } else if ["gnome", "unity", "budgie", "pantheon"].iter().any(|d| desktop.contains(d)) {
+            Box::new(Gsettings)
+        } else if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            Box::new(Swaybg::default())
+        } else {
+            Box::new(Feh)
+        }
+    }
+}
+
+async fn run(program: &str, args: &[&str]) -> Result<(), ArtError> {
+    let out = Command::new(program)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| ArtError::unsupported(format!("Failed to run {program}: {e}")))?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        return Err(ArtError::unsupported(format!("{program} failed: {}", stderr.trim())));
+    }
+    Ok(())
+}
+
+/// Writes artwork to disk and hands it to a backend
+pub struct Wallpaper {
+    backend: Box<dyn WallpaperBackend>,
+    dir: PathBuf,
+}
+
+impl Wallpaper {
+    /// Wallpaper images are kept in `dir`, replacing the previous one each time
+    pub fn new(backend: Box<dyn WallpaperBackend>, dir: PathBuf) -> Self {
+        log::info!("Using {} for wallpapers", backend.name());
+        Self { backend, dir }
+    }
+
+    /// Make `artwork` the desktop background. Returns the image path.
+    pub async fn apply(&self, artwork: &Artwork, image: &ImageData) -> Result<PathBuf, ArtError> {
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| ArtError::cache(format!("Failed to create {}: {e}", self.dir.display())))?;
+
+        // A new name each time; desktops ignore a path they've already applied
+        let path = self.dir.join(format!(
+            "{}-{}.{}",
+            file_stem(&artwork.id),
+            now_millis(),
+            extension_for_mime(&image.mime)
+        ));
+        tokio::fs::write(&path, &image.bytes)
+            .await
+            .map_err(|e| ArtError::cache(format!("Failed to write {}: {e}", path.display())))?;
+
+        self.backend.apply(&path).await?;
+        log::info!("Wallpaper set to {} via {}", artwork.title, self.backend.name());
+        self.remove_others(&path).await;
+        Ok(path)
+    }
+
+    /// Make an artwork we've already downloaded the desktop background
+    pub async fn apply_cached(&self, cache: &ArtCache, id: &str) -> Result<PathBuf, ArtError> {
+        let (artwork, image) = cache
+            .find_with_image(id)
+            .await
+            .ok_or_else(|| ArtError::cache(format!("No downloaded image for {id}")))?;
+        self.apply(&artwork, &image).await
+    }
+
+    /// Put new artwork on the desktop, fetched apart from the overlays' queue
+    pub async fn rotate(&self, cache: &ArtCache) -> Result<Artwork, ArtError> {
+        let artwork = cache.next_live(WALLPAPER_HISTORY).await?;
+        let image = cache
+            .image(&artwork.id)
+            .await
+            .ok_or_else(|| ArtError::cache(format!("No downloaded image for {}", artwork.id)))?;
+        self.apply(&artwork, &image).await?;
+        Ok(artwork)
+    }
+
+    async fn remove_others(&self, keep: &Path) {
+        let Ok(mut entries) = tokio::fs::read_dir(&self.dir).await else {
+            return;
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            if path != keep {
+                let _ = tokio::fs::remove_file(path).await;
+            }
+        }
+    }
+}
+
+fn since_epoch() -> Duration {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()
+}
+
+fn now_secs() -> u64 {
+    since_epoch().as_secs()
+}
+
+fn now_millis() -> u128 {
+    since_epoch().as_millis()
+}
+
+/// Whether a day has passed since `last` (seconds since the epoch), or it never ran
+fn rotation_due_at(last: Option<u64>, now: u64) -> bool {
+    last.map_or(true, |t| now.saturating_sub(t) >= ROTATE_EVERY.as_secs())
+}
+
+fn rotation_due(app: &AppHandle) -> bool {
+    let last = app
+        .store("settings.json")
+        .ok()
+        .and_then(|store| store.get(ROTATED_AT_KEY))
+        .and_then(|v| v.as_u64());
+    rotation_due_at(last, now_secs())
+}
+
+/// Put new artwork on the desktop and remember when
+pub async fn rotate(app: &AppHandle) -> Result<(), ArtError> {
+    app.state::<Wallpaper>()
+        .rotate(&app.state::<ArtCache>())
+        .await?;
+
+    let store = app.store("settings.json").map_err(ArtError::store)?;
+    store.set(ROTATED_AT_KEY, serde_json::Value::from(now_secs()));
+    Ok(())
+}
+
+/// Change the wallpaper once a day while `rotate_daily` is on
+pub fn start_rotation(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(ROTATION_CHECK).await;
+            if !WallpaperSettings::load(&app).rotate_daily || !rotation_due(&app) {
+                continue;
+            }
+            if let Err(e) = rotate(&app).await {
+                log::warn!("Failed to rotate wallpaper: {e}");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::art_api::{ArtSource, FetchContext, FetchedArtwork, SourceRegistry};
+    use crate::art_cache::{tests::artwork, SHARED_HISTORY};
+    use crate::disk_cache::DiskCache;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// Remembers every image it was asked to put on the desktop
+    #[derive(Clone, Default)]
+    struct RecordingBackend(Arc<Mutex<Vec<PathBuf>>>);
+
+    impl RecordingBackend {
+        fn applied(&self) -> Vec<PathBuf> {
+            self.0.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl WallpaperBackend for RecordingBackend {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn apply(&self, path: &Path) -> Result<(), ArtError> {
+            self.0.lock().unwrap().push(path.to_path_buf());
+            Ok(())
+        }
+    }
+
+    /// Hands out "t-1", "t-2"... with one-byte images
+    #[derive(Default)]
+    struct Counter(AtomicUsize);
+
+    #[async_trait]
+    impl ArtSource for Counter {
+        fn name(&self) -> &str {
+            "Counter"
+        }
+
+        fn id_prefix(&self) -> &str {
+            "t"
+        }
+
+        async fn fetch_random(&self, _ctx: &FetchContext) -> Result<FetchedArtwork, ArtError> {
+            let n = self.0.fetch_add(1, Ordering::Relaxed) + 1;
+            Ok(FetchedArtwork {
+                artwork: artwork(&format!("t-{n}")),
+                image: ImageData {
+                    bytes: vec![n as u8],
+                    mime: "image/png".into(),
+                },
+            })
+        }
+    }
+
+    struct Fixture {
+        cache: ArtCache,
+        wallpaper: Wallpaper,
+        backend: RecordingBackend,
+        dir: tempfile::TempDir,
+        _cache_dir: tempfile::TempDir,
+    }
+
+    fn fixture() -> Fixture {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let disk = DiskCache::open(cache_dir.path().into(), u64::MAX).unwrap();
+        let mut sources = SourceRegistry::new();
+        sources.register(Counter::default());
+        let dir = tempfile::tempdir().unwrap();
+        let backend = RecordingBackend::default();
+        Fixture {
+            cache: ArtCache::new(sources, disk, None),
+            wallpaper: Wallpaper::new(Box::new(backend.clone()), dir.path().into()),
+            backend,
+            dir,
+            _cache_dir: cache_dir,
+        }
+    }
+
+    fn files_in(dir: &Path) -> Vec<PathBuf> {
+        std::fs::read_dir(dir).unwrap().map(|e| e.unwrap().path()).collect()
+    }
+
+    #[tokio::test]
+    async fn sets_a_shown_artwork_as_wallpaper() {
+        let f = fixture();
+        let shown = f.cache.next(SHARED_HISTORY, None).await.unwrap();
+
+        let path = f.wallpaper.apply_cached(&f.cache, &shown.id).await.unwrap();
+        assert_eq!(f.backend.applied(), vec![path.clone()]);
+        assert_eq!(std::fs::read(&path).unwrap(), [1]);
+    }
+
+    #[tokio::test]
+    async fn refuses_artwork_that_was_never_downloaded() {
+        let f = fixture();
+        assert!(f.wallpaper.apply_cached(&f.cache, "t-9").await.is_err());
+        assert!(f.backend.applied().is_empty());
+    }
+
+    #[tokio::test]
+    async fn rotation_uses_a_new_file_each_time_and_keeps_only_the_latest() {
+        let f = fixture();
+        let first = f.wallpaper.rotate(&f.cache).await.unwrap();
+        let second = f.wallpaper.rotate(&f.cache).await.unwrap();
+        assert_ne!(first.id, second.id);
+
+        let applied = f.backend.applied();
+        assert_eq!(applied.len(), 2);
+        assert_ne!(applied[0], applied[1]);
+        assert_eq!(files_in(f.dir.path()), [applied[1].clone()]);
+        assert_eq!(f.cache.current(WALLPAPER_HISTORY).await.unwrap().id, second.id);
+        assert!(f.cache.current(SHARED_HISTORY).await.is_none());
+    }
+
+    #[test]
+    fn rotates_once_a_day() {
+        let day = ROTATE_EVERY.as_secs();
+        assert!(rotation_due_at(None, 1_000));
+        assert!(!rotation_due_at(Some(1_000), 1_000 + day - 1));
+        assert!(rotation_due_at(Some(1_000), 1_000 + day));
+    }
+}
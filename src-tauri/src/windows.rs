@@ -1,16 +1,140 @@
-use tauri::{AppHandle, Manager, WebviewWindowBuilder, WebviewUrl};
+use crate::art_api::ImageSize;
+use crate::error::ArtError;
+use crate::slideshow::Slideshow;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Monitor, WebviewWindowBuilder, WebviewUrl};
+use tauri_plugin_store::StoreExt;
+
+/// How artwork is laid out across multiple monitors
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplayMode {
+    /// Every monitor shows the same artwork
+    #[default]
+    Mirror,
+    /// Each monitor gets its own artwork and history
+    PerMonitor,
+    /// One artwork stretched across the combined area of all monitors
+    Span,
+}
+
+/// The part of a spanned artwork one overlay shows, in physical pixels.
+///
+/// The overlay lays the image out on a `total_width` x `total_height` canvas
+/// (the bounding box of all monitors) and shifts it by `x`, `y`, so gaps and
+/// offsets between monitors are kept.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct SpanSlice {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub total_width: u32,
+    pub total_height: u32,
+    /// Scale factor of this overlay's monitor, to convert to CSS pixels
+    pub scale_factor: f64,
+}
+
+/// Bounding box of all monitors in physical pixels: (left, top, width, height)
+fn desktop_bounds(monitors: &[Monitor]) -> Option<(i32, i32, u32, u32)> {
+    let left = monitors.iter().map(|m| m.position().x).min()?;
+    let top = monitors.iter().map(|m| m.position().y).min()?;
+    let right = monitors
+        .iter()
+        .map(|m| m.position().x + m.size().width as i32)
+        .max()?;
+    let bottom = monitors
+        .iter()
+        .map(|m| m.position().y + m.size().height as i32)
+        .max()?;
+    Some((left, top, (right - left) as u32, (bottom - top) as u32))
+}
+
+/// Slice of the spanned artwork shown by overlay window `label`
+pub fn span_slice(app: &AppHandle, label: &str) -> Option<SpanSlice> {
+    let index = monitor_index(label)?;
+    let monitors = app.available_monitors().ok()?;
+    let (left, top, total_width, total_height) = desktop_bounds(&monitors)?;
+    let monitor = monitors.get(index)?;
+    Some(SpanSlice {
+        x: monitor.position().x - left,
+        y: monitor.position().y - top,
+        width: monitor.size().width,
+        height: monitor.size().height,
+        total_width,
+        total_height,
+        scale_factor: monitor.scale_factor(),
+    })
+}
+
+/// The display mode saved in `settings.json`
+pub fn display_mode(app: &AppHandle) -> DisplayMode {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get("display_mode"))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+/// Labels the overlay windows get, one per connected monitor
+pub fn overlay_labels(app: &AppHandle) -> Vec<String> {
+    let count = app.available_monitors().map(|m| m.len()).unwrap_or(0);
+    (0..count).map(overlay_label).collect()
+}
+
+fn overlay_label(index: usize) -> String {
+    format!("overlay-{index}")
+}
+
+fn monitor_index(label: &str) -> Option<usize> {
+    label.strip_prefix("overlay-")?.parse().ok()
+}
+
+/// Size of the screen area artwork for history `key` is shown on: the overlay's
+/// own monitor for a window label, otherwise the overall target size
+pub fn screen_size(app: &AppHandle, key: &str) -> Option<ImageSize> {
+    let Some(index) = monitor_index(key) else {
+        return monitor_target_size(app);
+    };
+    let monitors = app.available_monitors().ok()?;
+    let size = monitors.get(index)?.size();
+    Some(ImageSize {
+        width: size.width,
+        height: size.height,
+    })
+}
+
+/// Image size that fills the largest connected monitor, or the whole desktop
+/// when spanning, in physical pixels
+pub fn monitor_target_size(app: &AppHandle) -> Option<ImageSize> {
+    let monitors = app.available_monitors().ok()?;
+    if display_mode(app) == DisplayMode::Span {
+        let (_, _, width, height) = desktop_bounds(&monitors)?;
+        return Some(ImageSize { width, height });
+    }
+    monitors
+        .iter()
+        .map(|m| ImageSize {
+            width: m.size().width,
+            height: m.size().height,
+        })
+        .reduce(|a, b| ImageSize {
+            width: a.width.max(b.width),
+            height: a.height.max(b.height),
+        })
+}
 
 /// Create overlay windows on all monitors
-pub fn create_overlay_windows(app: &AppHandle) -> Result<(), String> {
+pub fn create_overlay_windows(app: &AppHandle) -> Result<(), ArtError> {
     // Close any existing overlay windows first
     close_overlay_windows(app);
 
     let monitors = app
         .available_monitors()
-        .map_err(|e| format!("Failed to get monitors: {e}"))?;
+        .map_err(|e| ArtError::window(format!("Failed to get monitors: {e}")))?;
 
     for (i, monitor) in monitors.iter().enumerate() {
-        let label = format!("overlay-{i}");
+        let label = overlay_label(i);
 
         // If a window with this label still exists, destroy it first
         if let Some(existing) = app.get_webview_window(&label) {
@@ -47,7 +171,7 @@ pub fn create_overlay_windows(app: &AppHandle) -> Result<(), String> {
             .skip_taskbar(true)
             .visible(false)
             .build()
-            .map_err(|e| format!("Failed to create overlay {i}: {e}"))?;
+            .map_err(|e| ArtError::window(format!("Failed to create overlay {i}: {e}")))?;
     }
 
     // Switch to Regular activation policy so we receive keyboard events
@@ -85,8 +209,19 @@ pub fn show_overlay_windows(app: &AppHandle) {
     }
 }
 
+/// Whether any overlay window is currently open
+pub fn overlays_open(app: &AppHandle) -> bool {
+    app.webview_windows()
+        .keys()
+        .any(|label| label.starts_with("overlay-"))
+}
+
 /// Close all overlay windows
 pub fn close_overlay_windows(app: &AppHandle) {
+    if let Some(slideshow) = app.try_state::<Slideshow>() {
+        slideshow.stop();
+    }
+
     let windows: Vec<_> = app
         .webview_windows()
         .into_iter()
@@ -118,7 +253,7 @@ pub fn close_overlay_windows(app: &AppHandle) {
 }
 
 /// Create the settings window
-pub fn create_settings_window(app: &AppHandle) -> Result<(), String> {
+pub fn create_settings_window(app: &AppHandle) -> Result<(), ArtError> {
     // If settings already open, just focus it
     if let Some(win) = app.get_webview_window("settings") {
         let _ = win.set_focus();
@@ -133,10 +268,10 @@ pub fn create_settings_window(app: &AppHandle) -> Result<(), String> {
 
     WebviewWindowBuilder::new(app, "settings", url)
         .title("Art — Settings")
-        .inner_size(400.0, 300.0)
+        .inner_size(420.0, 720.0)
         .resizable(false)
         .build()
-        .map_err(|e| format!("Failed to create settings window: {e}"))?;
+        .map_err(|e| ArtError::window(format!("Failed to create settings window: {e}")))?;
 
     Ok(())
 }
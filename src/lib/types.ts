@@ -5,9 +5,151 @@ export interface Artwork {
   date: string;
   medium: string;
   source: string;
-  image_base64: string;
+  /** `art://` URL served by the backend from its image cache */
+  image_url: string;
+  /** Size (or proportions) of the source image, when known */
+  image_size?: { width: number; height: number };
+  credit_line?: string;
+  /** Physical dimensions as the museum writes them */
+  dimensions?: string;
+  department?: string;
+  culture?: string;
+  accession_number?: string;
+  public_domain?: boolean;
+  /** License name as the source gives it, e.g. "CC BY-SA 4.0" */
+  license?: string;
+  /** The artwork's page on the museum website */
+  object_url?: string;
 }
 
-export interface Settings {
-  hotkey: string;
+/** Things a global shortcut can do */
+export type HotkeyAction =
+  | "toggle"
+  | "next"
+  | "prev"
+  | "favorite"
+  | "open_in_browser"
+  | "pause_slideshow";
+
+/** Shortcut per action; missing or empty means unbound */
+export type Keymap = Partial<Record<HotkeyAction, string>>;
+
+/** How artwork is laid out across monitors */
+export type DisplayMode = "mirror" | "per_monitor" | "span";
+
+/** Whether and how often artwork is drawn from a source */
+export interface SourcePrefs {
+  enabled: boolean;
+  weight: number;
+  /** Search terms replacing the source's defaults */
+  terms?: string[];
+  /** Values for the source's filters, by key */
+  filters?: Record<string, string>;
+  /** Key for sources whose API requires one */
+  api_key?: string;
+  /** Directory for the local folder source */
+  folder?: string;
+}
+
+/** A source-specific filter such as the Met's departmentId */
+export interface SourceFilter {
+  key: string;
+  label: string;
+  numeric: boolean;
+  default: string | null;
+}
+
+/** An artwork source as listed in settings; `id` is its artwork id prefix */
+export interface SourceInfo extends SourcePrefs {
+  id: string;
+  name: string;
+  searchable: boolean;
+  needs_api_key: boolean;
+  needs_folder: boolean;
+  default_terms: string[];
+  available_filters: SourceFilter[];
+}
+
+/** Setting the desktop background from the collection */
+export interface WallpaperSettings {
+  rotate_daily: boolean;
+}
+
+/** Automatic advancing while the overlays are open */
+export interface SlideshowSettings {
+  enabled: boolean;
+  interval_secs: number;
+}
+
+/** Show art automatically after a period of inactivity */
+export interface ScreensaverSettings {
+  enabled: boolean;
+  idle_minutes: number;
+}
+
+/** Part of a spanned artwork one overlay shows, in physical pixels */
+export interface SpanSlice {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  total_width: number;
+  total_height: number;
+  scale_factor: number;
+}
+
+/** Error returned by backend commands, tagged by `kind` */
+export type ArtError =
+  | { kind: "network"; source: string; message: string }
+  | { kind: "http_status"; source: string; status: number; url: string }
+  | { kind: "parse"; source: string; message: string }
+  | { kind: "no_results"; source: string; message: string }
+  | { kind: "image_rejected"; url: string; reason: string }
+  | { kind: "all_sources_failed"; errors: ArtError[]; offline: boolean }
+  | { kind: "history_boundary"; message: string }
+  | { kind: "unsupported"; message: string }
+  | { kind: "invalid"; message: string }
+  | { kind: "store"; message: string }
+  | { kind: "cache"; message: string }
+  | { kind: "window"; message: string }
+  | { kind: "hotkey"; shortcut: string; problem: "invalid" | "duplicate" | "taken"; message: string };
+
+/** Short human-readable description of a backend error */
+export function describeError(e: unknown): string {
+  const err = e as ArtError;
+  switch (err?.kind) {
+    case "network":
+      return "Can't reach the museum servers. Check your connection.";
+    case "http_status":
+      return `${err.source} returned HTTP ${err.status}`;
+    case "parse":
+      return `${err.source} sent an unexpected response`;
+    case "no_results":
+      return `${err.source}: ${err.message}`;
+    case "image_rejected":
+      return `Image could not be loaded (${err.reason})`;
+    case "all_sources_failed":
+      return err.offline
+        ? "Can't reach the museum servers. Check your connection."
+        : "Couldn't find any artwork right now. Try again in a moment.";
+    case "hotkey":
+      switch (err.problem) {
+        case "invalid":
+          return `${err.shortcut} isn't a valid shortcut: ${err.message}`;
+        case "duplicate":
+          return `${err.shortcut} is ${err.message}`;
+        case "taken":
+          return `${err.shortcut} is already taken by another application`;
+      }
+      return err.message;
+    case "history_boundary":
+    case "unsupported":
+    case "invalid":
+    case "store":
+    case "cache":
+    case "window":
+      return err.message;
+    default:
+      return String(e);
+  }
 }